@@ -0,0 +1,90 @@
+use crate::prelude::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// A key for a content addressed store, derived from the crypto hash of a value.
+/// See content_id
+///
+/// The string form is the 32 bytes of the hash encoded as lowercase base32 with
+/// the RFC 4648 alphabet (a-z, 2-7) and no padding, which is always 52 characters.
+/// Each character holds 5 bits, most significant first, so the final character
+/// holds the last bit followed by 4 bits which are always 0. Parsing accepts only
+/// this canonical form, so each ContentId has exactly one string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ContentId(pub [u8; 32]);
+
+/// The ContentId of the value, from its crypto hash
+pub fn content_id<T: StableHash>(value: &T) -> ContentId {
+    profile_fn!(content_id);
+    ContentId(crate::crypto_stable_hash(value))
+}
+
+const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// The length of the string form
+const ENCODED_LEN: usize = (32 * 8usize).div_ceil(5);
+
+impl fmt::Display for ContentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut encoded = [0u8; ENCODED_LEN];
+        let mut buffer = 0u16;
+        let mut bits = 0;
+        let mut out = encoded.iter_mut();
+        for byte in self.0 {
+            buffer = (buffer << 8) | byte as u16;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                *out.next().unwrap() = ALPHABET[((buffer >> bits) & 31) as usize];
+            }
+        }
+        // The remaining bits, padded with zeros
+        *out.next().unwrap() = ALPHABET[((buffer << (5 - bits)) & 31) as usize];
+        f.write_str(std::str::from_utf8(&encoded).unwrap())
+    }
+}
+
+/// The string was not the canonical form of a ContentId
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseContentIdError;
+
+impl fmt::Display for ParseContentIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid content id")
+    }
+}
+
+impl std::error::Error for ParseContentIdError {}
+
+impl FromStr for ContentId {
+    type Err = ParseContentIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        profile_fn!(content_id_from_str);
+
+        if s.len() != ENCODED_LEN {
+            return Err(ParseContentIdError);
+        }
+        let mut decoded = [0u8; 32];
+        let mut buffer = 0u16;
+        let mut bits = 0;
+        let mut out = decoded.iter_mut();
+        for c in s.bytes() {
+            let value = ALPHABET
+                .iter()
+                .position(|&a| a == c)
+                .ok_or(ParseContentIdError)?;
+            buffer = (buffer << 5) | value as u16;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                *out.next().unwrap() = (buffer >> bits) as u8;
+            }
+        }
+        // Any bits left over are padding, and must be 0 to be canonical
+        if buffer & ((1 << bits) - 1) != 0 {
+            return Err(ParseContentIdError);
+        }
+        Ok(Self(decoded))
+    }
+}
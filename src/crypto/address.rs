@@ -34,6 +34,19 @@ impl FieldAddress for CryptoAddress {
 }
 
 impl CryptoAddress {
+    /// An address which no field can have, used by CryptoStableHasherV2.
+    #[cfg(feature = "crypto-v2")]
+    ///
+    /// Every child appends a varint of at least 1, and finish appends 0 before
+    /// the payload. So the bytes [0x80, 0] can only follow a field address here,
+    /// since they are not the start of any varint (the varint 0 with a redundant
+    /// continuation byte), and are not the marker 0.
+    pub(crate) fn empty_member(&self) -> Self {
+        let mut hasher = self.hasher.clone();
+        hasher.update(&[0x80]);
+        Self { hasher }
+    }
+
     pub(crate) fn finish(self, payload: &[u8]) -> OutputReader {
         profile_method!(finish);
 
@@ -53,4 +66,21 @@ impl CryptoAddress {
         hasher.update(payload);
         hasher.finalize_xof()
     }
+
+    /// Same as finish with the chunks concatenated
+    pub(crate) fn finish_chunks<'a>(
+        self,
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) -> OutputReader {
+        profile_method!(finish_chunks);
+
+        let Self { mut hasher, .. } = self;
+
+        // See also 91e48829-7bea-4426-971a-f092856269a5
+        hasher.update(&[0]);
+        for chunk in chunks {
+            hasher.update(chunk);
+        }
+        hasher.finalize_xof()
+    }
 }
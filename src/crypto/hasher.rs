@@ -1,6 +1,6 @@
 use super::address::CryptoAddress;
 use crate::prelude::*;
-use blake3::Hasher;
+use blake3::{Hasher, OutputReader};
 use ibig::UBig;
 use lazy_static::lazy_static;
 use num_traits::{identities::One, Zero};
@@ -66,13 +66,19 @@ impl StableHasher for CryptoStableHasher {
         profile_method!(write);
 
         // Write the field into a database cell
-        let mut output = field_address.finish(bytes);
-        // Extend to the length necessary. This is a 2048 bit value, 1 bit
-        // less than the prime the hash wraps around.
-        let mut digits = [0u8; 256];
-        output.fill(&mut digits);
-        let digits = UBig::from_le_bytes(&digits);
-        mul_mod_p(&mut self.value, &digits);
+        let output = field_address.finish(bytes);
+        self.mix_output(output);
+    }
+
+    fn write_chunks<'a>(
+        &mut self,
+        field_address: Self::Addr,
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) {
+        profile_method!(write_chunks);
+
+        let output = field_address.finish_chunks(chunks);
+        self.mix_output(output);
     }
 
     #[inline]
@@ -89,7 +95,7 @@ impl StableHasher for CryptoStableHasher {
         let mut y = &*P - UBig::from(2u32);
         while !y.is_zero() {
             todo.push(y.clone());
-            y = y / 2;
+            y /= 2;
         }
         let mut p = UBig::one();
         while let Some(next) = todo.pop() {
@@ -124,11 +130,28 @@ impl StableHasher for CryptoStableHasher {
         profile_method!(from_bytes);
 
         let value = UBig::from_le_bytes(&bytes);
-        assert!(&value <= &*P);
+        assert!(value <= *P);
         Self { value }
     }
 }
 
+impl CryptoStableHasher {
+    /// Whether nothing has been written, net of any mixed in or unmixed
+    #[cfg(feature = "crypto-v2")]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.value.is_one()
+    }
+
+    fn mix_output(&mut self, mut output: OutputReader) {
+        // Extend to the length necessary. This is a 2048 bit value, 1 bit
+        // less than the prime the hash wraps around.
+        let mut digits = [0u8; 256];
+        output.fill(&mut digits);
+        let digits = UBig::from_le_bytes(&digits);
+        mul_mod_p(&mut self.value, &digits);
+    }
+}
+
 #[cfg(test)]
 impl CryptoStableHasher {
     pub(crate) fn rand() -> Self {
@@ -137,13 +160,13 @@ impl CryptoStableHasher {
             let mut bytes = Vec::new();
             for _ in 0..257 {
                 let rand_byte: u8 = rand::thread_rng().gen();
-                if bytes.len() == 0 && rand_byte == 0 {
+                if bytes.is_empty() && rand_byte == 0 {
                     continue;
                 }
                 bytes.push(rand_byte);
             }
             let big = UBig::from_be_bytes(&bytes);
-            if &big >= &*P {
+            if big >= *P {
                 continue;
             }
             return CryptoStableHasher { value: big };
@@ -1,4 +1,8 @@
 mod address;
 mod hasher;
+#[cfg(feature = "crypto-v2")]
+mod v2;
 
 pub use hasher::CryptoStableHasher;
+#[cfg(feature = "crypto-v2")]
+pub use v2::CryptoStableHasherV2;
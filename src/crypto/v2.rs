@@ -0,0 +1,82 @@
+use super::address::CryptoAddress;
+use super::hasher::CryptoStableHasher;
+use crate::prelude::*;
+
+/// The same as CryptoStableHasher, except for how a member of an unordered
+/// collection which writes nothing (eg: 0, "", or None) is written.
+///
+/// CryptoStableHasher writes the state of each member at the address of the
+/// collection itself, and the state of an empty hasher serializes the same as
+/// the int 1. So a set with a single default member collides with the sequence
+/// of a single default item, and with 1. eg: HashSet {0u32}, vec![0u32] and 1u32
+/// all hash the same. This writes such a member at an address which no field
+/// can have instead.
+///
+/// Any value which contains an unordered collection with a default member
+/// hashes differently than with CryptoStableHasher. Every other value hashes
+/// the same, including any number of nested collections. Since changing
+/// CryptoStableHasher would change hashes which have already been persisted,
+/// this is a separate hasher rather than a fix to it.
+///
+/// Unstable, behind the crypto-v2 feature. See crypto_stable_hash_v2
+#[derive(Clone, Default, Eq, PartialEq, Debug)]
+pub struct CryptoStableHasherV2(CryptoStableHasher);
+
+impl StableHasher for CryptoStableHasherV2 {
+    type Out = [u8; 32];
+    type Addr = CryptoAddress;
+    type Bytes = Vec<u8>;
+
+    #[inline]
+    fn new() -> Self {
+        Self(CryptoStableHasher::new())
+    }
+
+    #[inline]
+    fn write(&mut self, field_address: Self::Addr, bytes: &[u8]) {
+        self.0.write(field_address, bytes)
+    }
+
+    #[inline]
+    fn write_chunks<'a>(
+        &mut self,
+        field_address: Self::Addr,
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) {
+        self.0.write_chunks(field_address, chunks)
+    }
+
+    fn write_member(&mut self, result_address: Self::Addr, member: &Self) -> usize {
+        profile_method!(write_member);
+
+        if member.0.is_empty() {
+            self.0.write(result_address.empty_member(), &[]);
+            0
+        } else {
+            self.0.write_member(result_address, &member.0)
+        }
+    }
+
+    #[inline]
+    fn mixin(&mut self, other: &Self) {
+        self.0.mixin(&other.0)
+    }
+
+    fn unmix(&mut self, other: &Self) {
+        self.0.unmix(&other.0)
+    }
+
+    fn finish(&self) -> Self::Out {
+        self.0.finish()
+    }
+
+    fn to_bytes(&self) -> Self::Bytes {
+        self.0.to_bytes()
+    }
+
+    /// Panics if the bytes are not in a valid format.
+    /// The only valid values are values returned from to_bytes()
+    fn from_bytes(bytes: Self::Bytes) -> Self {
+        Self(CryptoStableHasher::from_bytes(bytes))
+    }
+}
@@ -0,0 +1,92 @@
+//! Diagnostics for tracking down differences between hashes.
+//! None of these change the value of any hash.
+
+use crate::fast::{FastAddress, FastStableHasher};
+use crate::impls::{unordered_member_hasher, MapEntry};
+use crate::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Returns the serialized state of the independent hasher used for each entry
+/// of the map, sorted so that the output is deterministic. Two maps which hash
+/// to the same value produce the same list, and comparing the lists of two maps
+/// which do not shows which entries differ.
+pub fn debug_entry_hashes<K: StableHash, V: StableHash, S>(
+    map: &HashMap<K, V, S>,
+) -> Vec<[u8; 32]> {
+    profile_fn!(debug_entry_hashes);
+
+    let mut hashes: Vec<_> = map
+        .iter()
+        .map(|(key, value)| {
+            let (member_address, _) = FastAddress::root().unordered();
+            unordered_member_hasher::<FastStableHasher>(&MapEntry(key, value), member_address)
+                .to_bytes()
+        })
+        .collect();
+    hashes.sort_unstable();
+    hashes
+}
+
+/// Returns the fast hash of the key and of the value of each entry of the map,
+/// sorted by key hash. Unlike debug_entry_hashes, this shows whether an entry
+/// differs because of its key or its value. eg: if two maps have the same keys
+/// but one value was changed, exactly one pair differs and both pairs have the
+/// same key hash.
+pub fn debug_entry_pairs<K: StableHash, V: StableHash, S>(
+    map: &HashMap<K, V, S>,
+) -> Vec<(u128, u128)> {
+    profile_fn!(debug_entry_pairs);
+
+    let mut pairs: Vec<_> = map
+        .iter()
+        .map(|(key, value)| (crate::fast_stable_hash(key), crate::fast_stable_hash(value)))
+        .collect();
+    pairs.sort_unstable();
+    pairs
+}
+
+/// The serialized states of the independent hashers for each member of a single
+/// unordered collection, sorted.
+pub type MemberMultiset = Vec<Vec<u8>>;
+
+thread_local! {
+    static RECORDING: RefCell<Option<Vec<MemberMultiset>>> = const { RefCell::new(None) };
+}
+
+/// Calls f, recording the members of every unordered collection (eg: HashMap,
+/// HashSet) which is hashed on this thread in the meantime, with any backend.
+///
+/// Each collection is recorded as the sorted multiset of the serialized states of
+/// its members, which is exactly what it contributes to the hash. The list of
+/// collections is sorted as well. So when two runs which should agree produce
+/// different hashes, comparing (eg: logging) their records shows whether the
+/// members themselves differ, independent of iteration order. Recording does not
+/// change the value of any hash.
+pub fn record_unordered_members<R>(f: impl FnOnce() -> R) -> (R, Vec<MemberMultiset>) {
+    profile_fn!(record_unordered_members);
+
+    let outer = RECORDING.with(|recording| recording.borrow_mut().replace(Vec::new()));
+    let result = f();
+    let mut records = RECORDING.with(|recording| {
+        let mut recording = recording.borrow_mut();
+        let records = recording.take().unwrap_or_default();
+        *recording = outer;
+        records
+    });
+    records.sort_unstable();
+    (result, records)
+}
+
+pub(crate) fn is_recording() -> bool {
+    RECORDING.with(|recording| recording.borrow().is_some())
+}
+
+pub(crate) fn record(mut members: MemberMultiset) {
+    members.sort_unstable();
+    RECORDING.with(|recording| {
+        if let Some(records) = recording.borrow_mut().as_mut() {
+            records.push(members);
+        }
+    });
+}
@@ -10,6 +10,32 @@ impl FieldAddress for u128 {
 
         self.wrapping_mul(486_187_739).wrapping_add(number as u128)
     }
+    /// Uses the full 128 bits of the name's hash rather than the 62 bits
+    /// the default has available.
+    #[inline]
+    fn named_child(&self, name: &str) -> Self {
+        profile_method!(named_child);
+
+        let hash = xxhash_rust::xxh3::xxh3_128(name.as_bytes());
+        self.wrapping_mul(486_187_739).wrapping_add(hash)
+    }
+    #[inline]
+    fn unordered(&self) -> (Self, Self) {
+        (Self::root(), *self)
+    }
+}
+
+/// The same scheme as u128, but wrapping at 2^64. See FastAddr64StableHasher
+impl FieldAddress for u64 {
+    fn root() -> Self {
+        17
+    }
+    #[inline]
+    fn child(&self, number: u64) -> Self {
+        profile_method!(child);
+
+        self.wrapping_mul(486_187_739).wrapping_add(number)
+    }
     #[inline]
     fn unordered(&self) -> (Self, Self) {
         (Self::root(), *self)
@@ -57,4 +83,26 @@ mod test {
         recurse(root, 4, 50, &mut collector);
         assert_eq!(30831, collector.len());
     }
+
+    #[test]
+    fn named_child_is_stable() {
+        let root = u128::root();
+        assert_eq!(
+            161745101148472925293886522918569201173,
+            root.named_child("foo")
+        );
+        assert_eq!(root.named_child("foo"), root.named_child("foo"));
+    }
+
+    #[test]
+    fn named_children_do_not_collide() {
+        let root = u128::root();
+        let mut collector = HashSet::new();
+        for name in ["foo", "bar", "baz", "fo", "oo", "foo_bar", ""] {
+            assert!(collector.insert(root.named_child(name)));
+        }
+        for i in 0..1000 {
+            assert!(collector.insert(root.child(i)));
+        }
+    }
 }
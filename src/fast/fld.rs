@@ -1,6 +1,5 @@
-use uint::construct_uint;
-
 use super::u192::U192;
+use u256::U256;
 
 // Useful reading: https://kevinventullo.com/2018/12/24/hashing-unordered-sets-how-far-will-cleverness-take-you/
 // Followed by: https://jeremykun.com/2021/10/14/group-actions-and-hashing-unordered-multisets/
@@ -14,8 +13,11 @@ pub struct FldMix(U192);
 // We need to divide 2^192 by an integer (once) in order to calculate inverses mod 2^192.
 // Alternatively, is there a way to compute floor(p / q) and p % q
 // given floor(p - 1) / q and (p - 1) % q? That would help...
-construct_uint! {
-    pub struct U256(4);
+#[allow(clippy::manual_range_contains, clippy::assign_op_pattern)]
+mod u256 {
+    uint::construct_uint! {
+        pub struct U256(4);
+    }
 }
 
 impl FldMix {
@@ -58,7 +60,7 @@ impl FldMix {
         //convert to U256
         let mut x: U256 = U256([x.0[0], x.0[1], x.0[2], 0]);
 
-        debug_assert!(x.0[0] % 2 != 0, "Even numbers have no inverse mod 2^192");
+        debug_assert!(x.0[0] & 1 == 1, "Even numbers have no inverse mod 2^192");
 
         let mut b: U256 = U256([0, 0, 0, 1]);
         let modulus: U256 = b;
@@ -76,7 +78,7 @@ impl FldMix {
             prev_s = s;
 
             if quotient * s > tmp {
-                tmp = tmp + (U256([1, 0, 0, 0]) + (quotient * s) / modulus) * (modulus);
+                tmp += (U256([1, 0, 0, 0]) + (quotient * s) / modulus) * (modulus);
             }
 
             s = (tmp - quotient * s) % modulus;
@@ -84,7 +86,7 @@ impl FldMix {
             tmp = prev_t;
             prev_t = t;
             if quotient * t > tmp {
-                tmp = tmp + (U256([1, 0, 0, 0]) + (quotient * t) / modulus) * (modulus);
+                tmp += (U256([1, 0, 0, 0]) + (quotient * t) / modulus) * (modulus);
             }
             t = (tmp - quotient * t) % modulus;
 
@@ -96,6 +98,7 @@ impl FldMix {
         U192([prev_s.0[0], prev_s.0[1], prev_s.0[2]])
     }
 
+    #[inline]
     pub fn mix(&mut self, value: u128, seed: u64) {
         // See also 0d123631-c654-4246-8d26-092c21d43037
         let v0 = seed & (u64::MAX >> 1);
@@ -121,7 +124,7 @@ impl FldMix {
     }
 
     #[inline]
-    pub fn to_bytes(&self) -> [u8; 24] {
+    pub fn to_bytes(self) -> [u8; 24] {
         let mut bytes = [0; 24];
         bytes[0..8].copy_from_slice(&self.0 .0[0].to_le_bytes());
         bytes[8..16].copy_from_slice(&self.0 .0[1].to_le_bytes());
@@ -1,8 +1,22 @@
 use std::convert::TryInto;
 
 use super::fld::FldMix;
+use super::FastAddress;
 use crate::prelude::*;
 
+/// FastStableHasher is Send and Sync, but writing requires &mut so a single
+/// instance cannot be written to by multiple threads. Since hashers combine with
+/// mixin in any order, threads may each write to their own hasher and combine them
+/// afterward. See also ConcurrentStableHasher.
+///
+/// The same holds across processes or machines, since to_bytes is stable. eg: to
+/// hash a map too large for one machine, split it into shards with disjoint keys.
+/// Each worker hashes its shard at the root address into a new hasher, and sends
+/// the result of to_bytes to a coordinator. The coordinator restores each with
+/// from_bytes and combines them with mixin, which finishes with the same hash as
+/// fast_stable_hash of the whole map. This works because each entry of a map is
+/// hashed independently of the others. Other values must be split such that no
+/// field address is written by more than one worker.
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub struct FastStableHasher {
     mixer: FldMix,
@@ -21,11 +35,19 @@ impl FastStableHasher {
     }
 }
 
+impl FastStableHasher {
+    /// The number of fields written, net of any mixed in or unmixed
+    pub(crate) fn count(&self) -> u64 {
+        self.count
+    }
+}
+
 impl StableHasher for FastStableHasher {
     type Out = u128;
-    type Addr = u128;
+    type Addr = FastAddress;
     type Bytes = [u8; 32];
 
+    #[inline]
     fn new() -> Self {
         Self {
             mixer: FldMix::new(),
@@ -61,6 +83,7 @@ impl StableHasher for FastStableHasher {
         }
     }
 
+    #[inline]
     fn write(&mut self, field_address: Self::Addr, bytes: &[u8]) {
         profile_method!(write);
 
@@ -70,9 +93,31 @@ impl StableHasher for FastStableHasher {
         // Also considered: t1ha3, MetroHash, SipHasher24
         // For more information about XXH3, see this:
         // https://fastcompression.blogspot.com/2019/03/presenting-xxh3.html
-        let hash = xxhash_rust::xxh3::xxh3_128_with_seed(bytes, field_address as u64);
-        self.mixer.mix(hash, (field_address >> 64) as u64);
-        self.count += 1;
+        //
+        // There is intentionally no separate encoding for small payloads (eg: bool
+        // or a single discriminant byte). XXH3 already has a dedicated path for
+        // inputs of 16 bytes or less, and a different encoding would change the
+        // output. Most of the fixed cost per write was call overhead, which is
+        // addressed by the #[inline] above. See the wide_struct_small_fields benchmark.
+        self.write_split(split(field_address), bytes);
+    }
+
+    fn write_many<'a>(&mut self, fields: impl IntoIterator<Item = (Self::Addr, &'a [u8])>) {
+        profile_method!(write_many);
+
+        for (field_address, bytes) in fields {
+            self.write_split(split(field_address), bytes);
+        }
+    }
+
+    fn write_chunks<'a>(
+        &mut self,
+        field_address: Self::Addr,
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) {
+        profile_method!(write_chunks);
+
+        self.write_chunks_split(split(field_address), chunks);
     }
 
     fn finish(&self) -> u128 {
@@ -80,3 +125,37 @@ impl StableHasher for FastStableHasher {
         xxhash_rust::xxh3::xxh3_128_with_seed(&self.mixer.to_bytes(), self.count)
     }
 }
+
+impl FastStableHasher {
+    /// Writes a field given the seed and high bits of its address.
+    /// Shared with FastAddr64StableHasher, which splits its address differently.
+    #[inline(always)]
+    pub(super) fn write_split(&mut self, (seed, high): (u64, u64), bytes: &[u8]) {
+        let hash = xxhash_rust::xxh3::xxh3_128_with_seed(bytes, seed);
+        self.mixer.mix(hash, high);
+        self.count += 1;
+    }
+
+    /// Same as write_split with the chunks concatenated
+    pub(super) fn write_chunks_split<'a>(
+        &mut self,
+        (seed, high): (u64, u64),
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) {
+        // See also write. The streaming hasher gives the same result as hashing
+        // the concatenated chunks at once.
+        let mut hasher = xxhash_rust::xxh3::Xxh3::with_seed(seed);
+        for chunk in chunks {
+            hasher.update(chunk);
+        }
+        self.mixer.mix(hasher.digest128(), high);
+        self.count += 1;
+    }
+}
+
+/// The low 64 bits of the address seed the hash of the payload, and the high
+/// 64 bits are mixed in along with it.
+#[inline(always)]
+fn split(field_address: FastAddress) -> (u64, u64) {
+    (field_address as u64, (field_address >> 64) as u64)
+}
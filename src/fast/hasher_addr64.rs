@@ -0,0 +1,66 @@
+use super::FastStableHasher;
+use crate::prelude::*;
+
+/// The same as FastStableHasher, except that the field address is a u64 rather
+/// than a u128. This only narrows the address: deriving a child address is 64 bit
+/// arithmetic, but each payload is still hashed with 128 bit xxh3 and mixed into
+/// the same 192 bit state, and the output is still a u128. So it saves the 128
+/// bit multiply for each field on targets without native support for it, and not
+/// the cost of the hashing itself. The trade off is a greater chance that two
+/// paths collide. Where 128 bit arithmetic is native, it is no faster than
+/// FastStableHasher, so there is no entry point like fast_stable_hash for it.
+///
+/// The whole address seeds the hash of each payload, and there are no high bits
+/// to mix in. Once an address no longer fits in 64 bits (eg: a few levels of
+/// nesting deep), it wraps differently than with FastStableHasher, and so does
+/// the hash. The two must not be compared or combined with one another.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct FastAddr64StableHasher(FastStableHasher);
+
+impl StableHasher for FastAddr64StableHasher {
+    type Out = u128;
+    type Addr = u64;
+    type Bytes = [u8; 32];
+
+    #[inline]
+    fn new() -> Self {
+        Self(FastStableHasher::new())
+    }
+
+    fn mixin(&mut self, other: &Self) {
+        self.0.mixin(&other.0);
+    }
+
+    fn unmix(&mut self, other: &Self) {
+        self.0.unmix(&other.0);
+    }
+
+    fn to_bytes(&self) -> Self::Bytes {
+        self.0.to_bytes()
+    }
+
+    fn from_bytes(bytes: Self::Bytes) -> Self {
+        Self(FastStableHasher::from_bytes(bytes))
+    }
+
+    #[inline]
+    fn write(&mut self, field_address: Self::Addr, bytes: &[u8]) {
+        profile_method!(write);
+
+        self.0.write_split((field_address, 0), bytes);
+    }
+
+    fn write_chunks<'a>(
+        &mut self,
+        field_address: Self::Addr,
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) {
+        profile_method!(write_chunks);
+
+        self.0.write_chunks_split((field_address, 0), chunks);
+    }
+
+    fn finish(&self) -> u128 {
+        self.0.finish()
+    }
+}
@@ -1,6 +1,11 @@
 mod address;
-mod fld;
+pub(crate) mod fld;
 mod hasher;
+mod hasher_addr64;
 mod u192;
 
 pub use hasher::FastStableHasher;
+pub use hasher_addr64::FastAddr64StableHasher;
+
+/// The field address of FastStableHasher.
+pub type FastAddress = u128;
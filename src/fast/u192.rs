@@ -58,6 +58,8 @@ impl Add for U192 {
 
 impl Sub for U192 {
     type Output = Self;
+    // The borrows are summed before being subtracted
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn sub(self, other: Self) -> Self {
         let me = &self.0;
         let you = &other.0;
@@ -0,0 +1,39 @@
+//! C functions for hashing a byte buffer, so that code which is not written in
+//! Rust can compute the same hashes. The buffer is hashed with the AsBytes
+//! encoding, so the results match eg: fast_stable_hash(&AsBytes(bytes)).
+
+use crate::utils::AsBytes;
+use std::slice;
+
+/// Writes the 16 byte fast hash of the buffer to out_ptr, as a little endian u128.
+///
+/// # Safety
+/// ptr must be valid for reads of len bytes, unless len is 0 in which case it
+/// may be null. out_ptr must be valid for writes of 16 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn stable_hash_fast_bytes(ptr: *const u8, len: usize, out_ptr: *mut u8) {
+    let bytes = bytes_from_raw(ptr, len);
+    let hash = crate::fast_stable_hash(&AsBytes(bytes)).to_le_bytes();
+    std::ptr::copy_nonoverlapping(hash.as_ptr(), out_ptr, hash.len());
+}
+
+/// Writes the 32 byte crypto hash of the buffer to out_ptr.
+///
+/// # Safety
+/// ptr must be valid for reads of len bytes, unless len is 0 in which case it
+/// may be null. out_ptr must be valid for writes of 32 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn stable_hash_crypto_bytes(ptr: *const u8, len: usize, out_ptr: *mut u8) {
+    let bytes = bytes_from_raw(ptr, len);
+    let hash = crate::crypto_stable_hash(&AsBytes(bytes));
+    std::ptr::copy_nonoverlapping(hash.as_ptr(), out_ptr, hash.len());
+}
+
+unsafe fn bytes_from_raw<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+    // slice::from_raw_parts does not allow null, even for an empty slice
+    if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr, len)
+    }
+}
@@ -0,0 +1,12 @@
+use crate::prelude::*;
+use arrayvec::ArrayVec;
+
+/// Hashed exactly like Vec, so only the items which are present are hashed and
+/// the capacity N is not. An ArrayVec, Vec, or &[T] with the same items agree.
+impl<T: StableHash, const N: usize> StableHash for ArrayVec<T, N> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        (&self[..]).stable_hash(field_address, state)
+    }
+}
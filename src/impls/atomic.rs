@@ -0,0 +1,37 @@
+use crate::prelude::*;
+use std::sync::atomic::{
+    AtomicBool, AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicU16, AtomicU32,
+    AtomicU64, AtomicU8, AtomicUsize, Ordering,
+};
+
+// Atomics hash exactly like the value they contain at the time it is loaded.
+// Since ints are written in a canonical form regardless of their size, this
+// includes AtomicUsize and AtomicIsize, which hash the same on all platforms
+// even though their width differs.
+macro_rules! impl_atomic {
+    ($($A:ty),*) => {
+        $(
+            impl StableHash for $A {
+                fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+                    profile_method!(stable_hash);
+
+                    self.load(Ordering::SeqCst).stable_hash(field_address, state)
+                }
+            }
+        )*
+    }
+}
+
+impl_atomic!(
+    AtomicBool,
+    AtomicU8,
+    AtomicU16,
+    AtomicU32,
+    AtomicU64,
+    AtomicUsize,
+    AtomicI8,
+    AtomicI16,
+    AtomicI32,
+    AtomicI64,
+    AtomicIsize
+);
@@ -0,0 +1,31 @@
+use crate::prelude::*;
+use num_bigint::{BigInt, BigUint, Sign};
+
+/// Hashed with the canonical integer encoding, so values hash the same as
+/// fixed width ints (eg: a BigUint of 5 and 5u32). 0 is the default.
+impl StableHash for BigUint {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        AsInt {
+            is_negative: false,
+            little_endian: &self.to_bytes_le(),
+        }
+        .stable_hash(field_address, state)
+    }
+}
+
+/// Hashed with the canonical integer encoding, so values hash the same as
+/// fixed width ints (eg: a BigInt of -5 and -5i32). 0 is the default.
+impl StableHash for BigInt {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        let (sign, little_endian) = self.to_bytes_le();
+        AsInt {
+            is_negative: sign == Sign::Minus,
+            little_endian: &little_endian,
+        }
+        .stable_hash(field_address, state)
+    }
+}
@@ -0,0 +1,28 @@
+use crate::prelude::*;
+use std::collections::BinaryHeap;
+
+/// Hashed as an unordered collection, since the iteration order of a heap is
+/// arbitrary. Duplicate items each contribute to the hash. For a hash that
+/// matches the sorted items in a Vec, see SortedHeap.
+impl<T: StableHash> StableHash for BinaryHeap<T> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        super::unordered_unique_stable_hash(self.iter(), field_address, state)
+    }
+}
+
+/// Hashes the items of a heap in ascending order, exactly as a sorted Vec of
+/// the same items would. Unlike the BinaryHeap impl, this has a canonical order
+/// but requires sorting the items each time the value is hashed.
+pub struct SortedHeap<'a, T>(pub &'a BinaryHeap<T>);
+
+impl<T: StableHash + Ord> StableHash for SortedHeap<'_, T> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        let mut sorted: Vec<&T> = self.0.iter().collect();
+        sorted.sort_unstable();
+        sorted.stable_hash(field_address, state)
+    }
+}
@@ -0,0 +1,22 @@
+use crate::prelude::*;
+use bitvec::prelude::{BitOrder, BitSlice, BitStore, BitVec};
+
+/// The bits are packed into bytes as described in packed_bits_stable_hash. So,
+/// the hash depends only on the sequence of bits and not on the storage type or
+/// bit order of the BitSlice, or on the unused bits of the last storage word.
+/// An empty BitSlice is the default and writes nothing.
+impl<T: BitStore, O: BitOrder> StableHash for &BitSlice<T, O> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        packed_bits_stable_hash(self.iter().by_vals(), field_address, state)
+    }
+}
+
+impl<T: BitStore, O: BitOrder> StableHash for BitVec<T, O> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        (&self[..]).stable_hash(field_address, state)
+    }
+}
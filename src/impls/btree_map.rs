@@ -0,0 +1,13 @@
+use crate::prelude::*;
+use std::collections::BTreeMap;
+
+/// Hashed as an unordered collection, exactly like HashMap. This allows
+/// changing between map types without changing the hash.
+impl<K: StableHash, V: StableHash> StableHash for BTreeMap<K, V> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        let entries = self.iter().map(|(key, value)| super::MapEntry(key, value));
+        super::unordered_unique_stable_hash(entries, field_address, state)
+    }
+}
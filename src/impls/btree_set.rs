@@ -0,0 +1,19 @@
+use crate::prelude::*;
+use std::collections::BTreeSet;
+
+/// Hashed as an unordered collection, exactly like HashSet. This allows
+/// changing between set types, including in the keys of a map, without
+/// changing the hash.
+impl<T: StableHash> StableHash for BTreeSet<T> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        self.stable_hash_unordered(field_address, state)
+    }
+}
+
+impl<T: StableHash> UnorderedCollection for BTreeSet<T> {
+    fn members(&self) -> impl Iterator<Item = impl StableHash + '_> + '_ {
+        self.iter()
+    }
+}
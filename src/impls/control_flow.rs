@@ -0,0 +1,23 @@
+use crate::prelude::*;
+use std::ops::ControlFlow;
+
+/// Mirrors Result, with Continue in place of Ok and Break in place of Err. As with
+/// Result, the discriminant is always written so that Continue(default) and
+/// Break(default) are distinguishable from each other and from an absent field.
+impl<B: StableHash, C: StableHash> StableHash for ControlFlow<B, C> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        let variant = match self {
+            Self::Continue(value) => {
+                value.stable_hash(field_address.child(0), state);
+                0
+            }
+            Self::Break(value) => {
+                value.stable_hash(field_address.child(0), state);
+                1
+            }
+        };
+        state.write(field_address, &[variant]);
+    }
+}
@@ -0,0 +1,21 @@
+use crate::prelude::*;
+use std::borrow::Cow;
+
+/// Borrowed and owned values hash the same as the borrowed form.
+/// For example, Cow<[T]> hashes like &[T] and Cow<str> hashes like &str.
+/// Sized types hash like the type itself, so Cow<HashMap<K, V>> hashes like
+/// the HashMap, which does not depend on the order of its entries.
+impl<B> StableHash for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+    for<'b> &'b B: StableHash,
+{
+    const TYPE_ID: Option<u64> = <&B as StableHash>::TYPE_ID;
+    const IS_UNIT: bool = <&B as StableHash>::IS_UNIT;
+
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        (&**self).stable_hash(field_address, state)
+    }
+}
@@ -0,0 +1,23 @@
+use crate::prelude::*;
+use rust_decimal::Decimal;
+
+/// The value is normalized before hashing, so that representations of the same
+/// number with a different scale (eg: 1.0 and 1.00) hash the same.
+///
+/// The mantissa is hashed as an integer at the field address, and the scale as an
+/// integer at child(1). Since the scale of a whole number is 0, a whole number
+/// hashes the same as the equivalent integer (eg: 5u32), making it possible to
+/// migrate a field from an int to a Decimal. As with ints, 0 is the default and
+/// does not contribute to the hash.
+impl StableHash for Decimal {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        // This also converts -0 to 0
+        let normalized = self.normalize();
+        normalized
+            .scale()
+            .stable_hash(field_address.child(1), state);
+        normalized.mantissa().stable_hash(field_address, state);
+    }
+}
@@ -22,3 +22,37 @@ struct Finite<T> {
 */
 
 // TODO: Test which exhaustively verifies all f32 bit patterns hash to the same values as (f32 as f64)
+
+use crate::prelude::*;
+use std::num::FpCategory;
+
+/// Hashes a float by its category together with a canonical form of its bits,
+/// such that values which are equivalent for most numerical purposes collide:
+/// * 0.0 and -0.0 collide, and are the default
+/// * All NaNs collide, regardless of sign or payload
+/// * Subnormals are a category of their own, and keep their exact bits
+/// * Normal numbers and infinities keep their exact bits, including the sign
+///
+/// There is no StableHash impl for f32 or f64 themselves yet (see above). That
+/// encoding is intended to support backward compatible changes in precision,
+/// which this does not attempt. This is only for f64, but every f32 converts to
+/// f64 exactly, so f32 values may be hashed as `CanonicalFloat(value as f64)`.
+#[derive(Clone, Copy, Debug)]
+pub struct CanonicalFloat(pub f64);
+
+impl StableHash for CanonicalFloat {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        let (category, bits) = match self.0.classify() {
+            // Zero must be discriminant 0 with no bits to be the default
+            FpCategory::Zero => (0u8, 0u64),
+            FpCategory::Normal => (1, self.0.to_bits()),
+            FpCategory::Subnormal => (2, self.0.to_bits()),
+            FpCategory::Infinite => (3, self.0.to_bits()),
+            FpCategory::Nan => (4, 0),
+        };
+        category.stable_hash(field_address.child(0), state);
+        bits.stable_hash(field_address.child(1), state);
+    }
+}
@@ -5,6 +5,29 @@ impl<K: StableHash, V: StableHash, S> StableHash for HashMap<K, V, S> {
     fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
         profile_method!(stable_hash);
 
-        super::unordered_unique_stable_hash(self.iter(), field_address, state)
+        let entries = self.iter().map(|(key, value)| super::MapEntry(key, value));
+        super::unordered_unique_stable_hash(entries, field_address, state)
     }
 }
+
+/// Hashes only the entries of the map for which pred returns true, exactly as
+/// the map with only those entries would be hashed, but without building it.
+/// The entries are filtered as they are visited, so nothing is copied.
+pub fn stable_hash_filtered<K, V, S, H>(
+    map: &HashMap<K, V, S>,
+    pred: impl Fn(&K, &V) -> bool,
+    field_address: H::Addr,
+    state: &mut H,
+) where
+    K: StableHash,
+    V: StableHash,
+    H: StableHasher,
+{
+    profile_fn!(stable_hash_filtered);
+
+    let entries = map
+        .iter()
+        .filter(|(key, value)| pred(key, value))
+        .map(|(key, value)| super::MapEntry(key, value));
+    super::unordered_unique_stable_hash(entries, field_address, state)
+}
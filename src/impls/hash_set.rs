@@ -5,6 +5,12 @@ impl<T: StableHash, S> StableHash for HashSet<T, S> {
     fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
         profile_method!(stable_hash);
 
-        super::unordered_unique_stable_hash(self.iter(), field_address, state)
+        self.stable_hash_unordered(field_address, state)
+    }
+}
+
+impl<T: StableHash, S> UnorderedCollection for HashSet<T, S> {
+    fn members(&self) -> impl Iterator<Item = impl StableHash + '_> + '_ {
+        self.iter()
     }
 }
@@ -1,27 +1,33 @@
 use crate::prelude::*;
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
 
+// These write exactly what AsInt would, but find the trimmed length of the
+// magnitude from its leading zeros rather than by scanning the bytes.
+// See also trim_zeros, and the single_primitive benchmark.
 macro_rules! impl_int {
     ($P:ty, $N:ty) => {
         impl StableHash for $P {
             fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
                 profile_method!(stable_hash);
 
-                AsInt {
-                    is_negative: false,
-                    little_endian: &self.to_le_bytes(),
+                let len = (<$P>::BITS - self.leading_zeros()).div_ceil(8) as usize;
+                if len != 0 {
+                    state.write(field_address, &self.to_le_bytes()[..len]);
                 }
-                .stable_hash(field_address, state)
             }
         }
         impl StableHash for $N {
             fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
                 profile_method!(stable_hash);
 
-                AsInt {
-                    is_negative: self.is_negative(),
-                    little_endian: &self.wrapping_abs().to_le_bytes(),
+                // See also AsInt
+                if self.is_negative() {
+                    state.write(field_address.child(0), &[]);
                 }
-                .stable_hash(field_address, state)
+                self.unsigned_abs().stable_hash(field_address, state)
             }
         }
     };
@@ -33,3 +39,45 @@ impl_int!(u32, i32);
 impl_int!(u16, i16);
 impl_int!(u8, i8);
 impl_int!(usize, isize);
+
+// NonZero types hash exactly like the int they contain, so eg: changing a field
+// between u32 and NonZeroU32 is backward compatible for every nonzero value.
+//
+// Since 0 is the only int which writes nothing, a NonZero value always contributes
+// to the hash. There is no default to skip, so a field which may be absent should
+// be an Option. Option<NonZeroU32> writes nothing for None, while Some(1) is
+// written like any other Some. The niche also makes NonZero a valid type for
+// CompactOption, where None hashes like 0 and Some(n) hashes like n. So a u32
+// field which used 0 to mean absent can become CompactOption<NonZeroU32> without
+// changing its hash.
+macro_rules! impl_non_zero {
+    ($($T:ty),*) => {
+        $(
+            impl StableHash for $T {
+                #[inline]
+                fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+                    profile_method!(stable_hash);
+
+                    self.get().stable_hash(field_address, state)
+                }
+            }
+        )*
+    };
+}
+
+impl_non_zero!(
+    NonZeroU128,
+    NonZeroU64,
+    NonZeroU32,
+    NonZeroU16,
+    NonZeroU8,
+    NonZeroUsize
+);
+impl_non_zero!(
+    NonZeroI128,
+    NonZeroI64,
+    NonZeroI32,
+    NonZeroI16,
+    NonZeroI8,
+    NonZeroIsize
+);
@@ -0,0 +1,34 @@
+use super::vec::sequence_stable_hash;
+use crate::prelude::*;
+use std::collections::LinkedList;
+
+/// Hashed exactly like Vec, so the order of the items matters.
+///
+/// Because each item is written at the address of its index, the hash of a
+/// list cannot be combined with the hash of another list to get the hash of
+/// the two spliced together. Splicing shifts the index of every item in the
+/// second list, and the length is written at the parent address. To hash a
+/// splice without building the combined list, see concat_hash.
+impl<T: StableHash> StableHash for LinkedList<T> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        sequence_stable_hash(self.iter(), field_address, state)
+    }
+}
+
+/// Hashes the items of a followed by the items of b, exactly as the list from
+/// a.append(b) would be hashed, but without modifying either list.
+///
+/// The items of b are written at indices shifted by the length of a, so they
+/// are still hashed again here. This saves the splice, not the work of hashing.
+pub fn concat_hash<T: StableHash, H: StableHasher>(
+    a: &LinkedList<T>,
+    b: &LinkedList<T>,
+    field_address: H::Addr,
+    state: &mut H,
+) {
+    profile_fn!(concat_hash);
+
+    sequence_stable_hash(a.iter().chain(b.iter()), field_address, state)
+}
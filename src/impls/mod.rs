@@ -1,34 +1,129 @@
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+mod atomic;
+#[cfg(feature = "num-bigint")]
+mod bigint;
+pub(crate) mod binary_heap;
+#[cfg(feature = "bitvec")]
+mod bitvec;
 mod bool;
-mod floats;
-mod hash_map;
+mod btree_map;
+mod btree_set;
+mod control_flow;
+mod cow;
+#[cfg(feature = "rust_decimal")]
+mod decimal;
+pub(crate) mod floats;
+pub(crate) mod hash_map;
 mod hash_set;
 mod ints;
-mod option;
+pub(crate) mod linked_list;
+mod net;
+#[cfg(feature = "time")]
+mod offset_date_time;
+mod once;
+pub(crate) mod option;
+#[cfg(feature = "ordered-float")]
+mod ordered_float;
+mod ordering;
+#[cfg(feature = "parking_lot")]
+mod parking_lot;
+mod poll;
+mod range;
+mod result;
+#[cfg(feature = "serde")]
+pub(crate) mod serde;
+mod smart_pointers;
 mod string;
+mod time;
 mod tuple;
-mod vec;
+#[cfg(feature = "unicode-normalization")]
+pub(crate) mod unicode;
+pub(crate) mod vec;
+mod weak;
 
 use crate::prelude::*;
 
-pub(self) fn unordered_unique_stable_hash<H: StableHasher>(
+pub(crate) fn unordered_unique_stable_hash<H: StableHasher>(
     items: impl Iterator<Item = impl StableHash>,
     field_address: H::Addr,
     state: &mut H,
 ) {
     profile_fn!(unordered_unique_stable_hash);
 
+    // There is intentionally no separate path for small collections. With
+    // FastStableHasher the member hasher and its serialized state both live
+    // on the stack, so there is no per-member heap allocation to avoid, and
+    // a second path would risk not matching this one exactly.
+    // See the small_maps benchmark.
+    #[cfg(feature = "debug")]
+    let mut recorded = crate::debug::is_recording().then(Vec::new);
+
     for member in items {
-        // Must create an independent hasher to "break" relationship between
-        // independent field addresses.
-        // See also a817fb02-7c77-41d6-98e4-dee123884287
-        let mut new_hasher = H::new();
         let (a, b) = field_address.unordered();
-        member.stable_hash(a, &mut new_hasher);
-        state.write(b, new_hasher.to_bytes().as_ref());
+        let new_hasher: H = unordered_member_hasher(&member, a);
+        #[cfg(feature = "debug")]
+        if let Some(recorded) = &mut recorded {
+            recorded.push(new_hasher.to_bytes().as_ref().to_vec());
+        }
+        state.write_member(b, &new_hasher);
+    }
+
+    #[cfg(feature = "debug")]
+    if let Some(recorded) = recorded {
+        crate::debug::record(recorded);
     }
 }
 
-impl<'a, T: StableHash> StableHash for &'a T {
+/// A single entry of a map, as hashed for each member of the map.
+///
+/// A map to () is used as a set, and is hashed as the set of its keys so that it
+/// matches HashSet. Otherwise each key would be written at child(0) of its entry,
+/// rather than at the member address as a set does.
+pub(crate) struct MapEntry<'a, K, V>(pub &'a K, pub &'a V);
+
+impl<K: StableHash, V: StableHash> StableHash for MapEntry<'_, K, V> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        if V::IS_UNIT {
+            self.0.stable_hash(field_address, state)
+        } else {
+            (self.0, self.1).stable_hash(field_address, state)
+        }
+    }
+}
+
+/// Hashes a single member of an unordered collection into its own hasher.
+/// The serialized state of the returned hasher is what gets written for the member.
+pub(crate) fn unordered_member_hasher<H: StableHasher>(
+    member: &impl StableHash,
+    member_address: H::Addr,
+) -> H {
+    // Must create an independent hasher to "break" relationship between
+    // independent field addresses.
+    // See also a817fb02-7c77-41d6-98e4-dee123884287
+    let mut new_hasher = H::new();
+    member.stable_hash(member_address, &mut new_hasher);
+    new_hasher
+}
+
+/// The contribution of a single entry to the hash of a map at the root address,
+/// as a hasher which can be combined with others using mixin and unmix. Mixing in
+/// the contributions of every entry gives the same state as hashing the map.
+pub(crate) fn root_map_entry<H: StableHasher, K: StableHash, V: StableHash>(
+    key: &K,
+    value: &V,
+) -> H {
+    let (member_address, result_address) = H::Addr::root().unordered();
+    let member: H = unordered_member_hasher(&MapEntry(key, value), member_address);
+    let mut entry = H::new();
+    entry.write_member(result_address, &member);
+    entry
+}
+
+impl<T: StableHash> StableHash for &T {
+    const TYPE_ID: Option<u64> = T::TYPE_ID;
+    const IS_UNIT: bool = T::IS_UNIT;
+
     #[inline]
     fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
         profile_method!(stable_hash);
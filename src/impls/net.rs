@@ -0,0 +1,68 @@
+use crate::prelude::*;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+impl StableHash for Ipv4Addr {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        AsBytes(&self.octets()).stable_hash(field_address, state)
+    }
+}
+
+impl StableHash for Ipv6Addr {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        AsBytes(&self.octets()).stable_hash(field_address, state)
+    }
+}
+
+/// Hashes the same as the inner address. A discriminant is not necessary
+/// because the number of octets differs between v4 and v6. This allows
+/// migrating a field from Ipv4Addr or Ipv6Addr to IpAddr.
+impl StableHash for IpAddr {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        match self {
+            Self::V4(addr) => addr.stable_hash(field_address, state),
+            Self::V6(addr) => addr.stable_hash(field_address, state),
+        }
+    }
+}
+
+impl StableHash for SocketAddrV4 {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        self.ip().stable_hash(field_address.child(0), state);
+        self.port().stable_hash(field_address.child(1), state);
+    }
+}
+
+/// The scope_id is hashed, since it identifies the interface that a link-local
+/// address belongs to and so is part of the identity of the address.
+/// The flowinfo is not hashed, since it labels a flow of traffic rather than
+/// identifying an endpoint. So, two addresses differing only in flowinfo
+/// hash the same.
+impl StableHash for SocketAddrV6 {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        self.ip().stable_hash(field_address.child(0), state);
+        self.port().stable_hash(field_address.child(1), state);
+        self.scope_id().stable_hash(field_address.child(2), state);
+    }
+}
+
+/// Hashes the same as the inner address. See also IpAddr.
+impl StableHash for SocketAddr {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        match self {
+            Self::V4(addr) => addr.stable_hash(field_address, state),
+            Self::V6(addr) => addr.stable_hash(field_address, state),
+        }
+    }
+}
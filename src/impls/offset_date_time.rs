@@ -0,0 +1,49 @@
+use crate::prelude::*;
+use ::time::{Date, OffsetDateTime, Time};
+use std::time::Duration;
+
+/// Hashed as the instant, normalized to UTC, exactly like the SystemTime of the
+/// same instant: a struct { magnitude, before_epoch } where magnitude is the
+/// Duration between the instant and the unix epoch. The offset is not hashed, so
+/// the same instant in any two offsets hashes the same, eg: 12:00 at +02:00 and
+/// 10:00 at UTC. Since the offset is lost, a value which must keep its local
+/// time should hash the offset as a separate field.
+impl StableHash for OffsetDateTime {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        // See also the SystemTime impl
+        let nanos = self.unix_timestamp_nanos();
+        let magnitude = nanos.unsigned_abs();
+        let magnitude = Duration::new(
+            (magnitude / 1_000_000_000) as u64,
+            (magnitude % 1_000_000_000) as u32,
+        );
+        magnitude.stable_hash(field_address.child(0), state);
+        (nanos < 0).stable_hash(field_address.child(1), state);
+    }
+}
+
+/// Hashed like a struct { year, month, day } where month is 1 to 12.
+impl StableHash for Date {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        self.year().stable_hash(field_address.child(0), state);
+        (self.month() as u8).stable_hash(field_address.child(1), state);
+        self.day().stable_hash(field_address.child(2), state);
+    }
+}
+
+/// Hashed like a struct { hour, minute, second, nanosecond }. So, midnight is
+/// the default.
+impl StableHash for Time {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        self.hour().stable_hash(field_address.child(0), state);
+        self.minute().stable_hash(field_address.child(1), state);
+        self.second().stable_hash(field_address.child(2), state);
+        self.nanosecond().stable_hash(field_address.child(3), state);
+    }
+}
@@ -0,0 +1,35 @@
+use crate::prelude::*;
+use std::cell::OnceCell;
+use std::sync::OnceLock;
+
+// An initialized cell hashes exactly like its value, and an uninitialized one is
+// the default and writes nothing, like an absent value. Note that this means an
+// uninitialized cell collides with a cell initialized to a default value (eg: 0),
+// unlike None and Some(0). Hashing never initializes the cell, so the hash of a
+// lazily initialized value can change once it is first accessed.
+
+impl<T: StableHash> StableHash for OnceCell<T> {
+    const TYPE_ID: Option<u64> = T::TYPE_ID;
+    const IS_UNIT: bool = T::IS_UNIT;
+
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        if let Some(value) = self.get() {
+            value.stable_hash(field_address, state);
+        }
+    }
+}
+
+impl<T: StableHash> StableHash for OnceLock<T> {
+    const TYPE_ID: Option<u64> = T::TYPE_ID;
+    const IS_UNIT: bool = T::IS_UNIT;
+
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        if let Some(value) = self.get() {
+            value.stable_hash(field_address, state);
+        }
+    }
+}
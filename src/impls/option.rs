@@ -10,3 +10,29 @@ impl<T: StableHash> StableHash for Option<T> {
         }
     }
 }
+
+/// Like Option, but without the write which marks that the value is Some.
+/// Some(value) hashes exactly like the value itself, and None writes nothing.
+/// This halves the number of fields written for deeply optional structures.
+///
+/// Precondition: every value of T, including its default, must write at least
+/// one field when hashed. Otherwise Some of a value which writes nothing cannot
+/// be told apart from None. This is not the case for most types in this crate,
+/// which skip their defaults (eg: 0, false, "", vec![], and None), so eg:
+/// CompactOption(Some(0u32)) and CompactOption(None) collide. Use Option for
+/// those. Types which are valid include one that always writes a tag, or
+/// Option itself, since Some(x) always writes a field.
+///
+/// The hash is not the same as Option of the same value, so changing a field
+/// between the two is not backward compatible.
+pub struct CompactOption<T>(pub Option<T>);
+
+impl<T: StableHash> StableHash for CompactOption<T> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        if let Some(value) = &self.0 {
+            value.stable_hash(field_address, state);
+        }
+    }
+}
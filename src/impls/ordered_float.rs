@@ -0,0 +1,23 @@
+use super::floats::CanonicalFloat;
+use crate::prelude::*;
+use ordered_float::OrderedFloat;
+
+/// Hashes the same as CanonicalFloat, so that the hash agrees with the Eq impl of
+/// OrderedFloat: all NaNs are equal and hash the same, as do 0.0 and -0.0. This
+/// makes OrderedFloat usable as the key of a hashed map, eg: histogram bins.
+impl StableHash for OrderedFloat<f64> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        CanonicalFloat(self.0).stable_hash(field_address, state)
+    }
+}
+
+/// Every f32 converts to f64 exactly, so this hashes the same as the f64 value.
+impl StableHash for OrderedFloat<f32> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        CanonicalFloat(self.0 as f64).stable_hash(field_address, state)
+    }
+}
@@ -0,0 +1,17 @@
+use crate::prelude::*;
+use std::cmp::Ordering;
+
+impl StableHash for Ordering {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        // Equal is the default, so that eg: a cached comparison that found no
+        // difference contributes nothing.
+        let variant = match self {
+            Self::Equal => return,
+            Self::Less => 1,
+            Self::Greater => 2,
+        };
+        state.write(field_address, &[variant]);
+    }
+}
@@ -0,0 +1,34 @@
+use crate::prelude::*;
+use parking_lot::{Mutex, RwLock};
+
+// Locks are transparent like smart pointers, so eg: Mutex<T> hashes exactly like
+// T. The lock is held while the value is hashed. For RwLock this is a read lock,
+// so other readers are not blocked.
+//
+// Unlike the std locks, parking_lot locks are never poisoned, so hashing never
+// panics because another thread panicked while holding the lock. The value is
+// hashed as the panicking thread left it. Hashing a Mutex which is already
+// locked by the current thread deadlocks, as does hashing an RwLock which the
+// current thread holds a write lock on.
+
+impl<T: StableHash> StableHash for Mutex<T> {
+    const TYPE_ID: Option<u64> = T::TYPE_ID;
+    const IS_UNIT: bool = T::IS_UNIT;
+
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        (*self.lock()).stable_hash(field_address, state)
+    }
+}
+
+impl<T: StableHash> StableHash for RwLock<T> {
+    const TYPE_ID: Option<u64> = T::TYPE_ID;
+    const IS_UNIT: bool = T::IS_UNIT;
+
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        (*self.read()).stable_hash(field_address, state)
+    }
+}
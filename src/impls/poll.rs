@@ -0,0 +1,21 @@
+use crate::prelude::*;
+use std::task::Poll;
+
+/// Hashed like an enum from impl_stable_hash_enum with Pending = 0 and
+/// Ready(value) = 1. The payload of Ready is written at child(0), and then the
+/// discriminant is written at the parent address as an int. So, like None,
+/// Pending is the default, while Ready(default) still writes its discriminant.
+impl<T: StableHash> StableHash for Poll<T> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        let variant: u64 = match self {
+            Poll::Pending => 0,
+            Poll::Ready(value) => {
+                value.stable_hash(field_address.child(0), state);
+                1
+            }
+        };
+        variant.stable_hash(field_address, state);
+    }
+}
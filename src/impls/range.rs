@@ -0,0 +1,58 @@
+use crate::prelude::*;
+use std::ops::{
+    Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+};
+
+impl<T: StableHash> StableHash for Bound<T> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        let variant = match self {
+            Self::Unbounded => return,
+            Self::Included(value) => {
+                value.stable_hash(field_address.child(0), state);
+                1
+            }
+            Self::Excluded(value) => {
+                value.stable_hash(field_address.child(0), state);
+                2
+            }
+        };
+        state.write(field_address, &[variant]);
+    }
+}
+
+/// All ranges are hashed as the pair of their start and end bounds. This gives
+/// each kind of range a distinct encoding (eg: 5.., ..5, and 5..5 do not collide)
+/// while two values describing the same bounds hash the same regardless of type.
+/// Since bounds are written at child addresses, a range never collides with a
+/// number. RangeFull has only unbounded ends, so is the default.
+fn range_stable_hash<T: StableHash, R: RangeBounds<T>, H: StableHasher>(
+    range: &R,
+    field_address: H::Addr,
+    state: &mut H,
+) {
+    (range.start_bound(), range.end_bound()).stable_hash(field_address, state)
+}
+
+macro_rules! impl_range {
+    ($($R:ident),*) => {
+        $(
+            impl<T: StableHash> StableHash for $R<T> {
+                fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+                    profile_method!(stable_hash);
+
+                    range_stable_hash(self, field_address, state)
+                }
+            }
+        )*
+    }
+}
+
+impl_range!(Range, RangeInclusive, RangeFrom, RangeTo, RangeToInclusive);
+
+impl StableHash for RangeFull {
+    fn stable_hash<H: StableHasher>(&self, _field_address: H::Addr, _state: &mut H) {
+        profile_method!(stable_hash);
+    }
+}
@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+impl<T: StableHash, E: StableHash> StableHash for Result<T, E> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        let variant = match self {
+            Ok(value) => {
+                value.stable_hash(field_address.child(0), state);
+                0
+            }
+            Err(err) => {
+                err.stable_hash(field_address.child(0), state);
+                1
+            }
+        };
+        // Unlike other enums, neither variant is treated as the default.
+        // The discriminant is always written so that Ok(default) and Err(default)
+        // are distinguishable from each other and from an absent field.
+        state.write(field_address, &[variant]);
+    }
+}
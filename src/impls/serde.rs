@@ -0,0 +1,699 @@
+use crate::prelude::*;
+use ::serde::ser::{self, Serialize};
+use std::fmt;
+
+/// Hashes any value implementing serde's Serialize using the same encoding
+/// as the StableHash impls in this crate:
+/// * structs and tuples write each field at child(index), like impl_stable_hash!
+/// * sequences are ordered and write their length, like Vec
+/// * maps are unordered, like HashMap
+/// * Option and the primitive types match their StableHash impls
+/// * newtype structs are transparent
+/// * enum variants write their payload at child(0), followed by the variant index
+///   at the parent. The first variant with no payload is the default.
+///
+/// Floats are not supported, since there is not yet a StableHash encoding for
+/// them. See also src/impls/floats.rs
+///
+/// A value is wrapped with try_new, which serializes it once without hashing,
+/// and returns an error if it contains a float or its Serialize impl fails. So,
+/// hashing a wrapped value does not fail, provided that its Serialize impl
+/// behaves the same each time it is called.
+pub struct SerdeStableHash<T>(T);
+
+impl<T: Serialize> SerdeStableHash<T> {
+    pub fn try_new(value: T) -> Result<Self, Error> {
+        profile_method!(try_new);
+
+        value.serialize(Serializer {
+            field_address: Unaddressed,
+            state: &mut Validator,
+        })?;
+        Ok(Self(value))
+    }
+}
+
+impl<T> SerdeStableHash<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Serialize> StableHash for SerdeStableHash<T> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        let serializer = Serializer {
+            field_address,
+            state,
+        };
+        if let Err(err) = self.0.serialize(serializer) {
+            // try_new already serialized the value successfully
+            panic!("Serialize impl is not deterministic: {}", err);
+        }
+    }
+}
+
+/// The reason a value cannot be hashed via serde, eg: because it contains a float
+#[derive(Debug)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// Discards every write, so that try_new can check that a value serializes
+/// without paying for hashing it.
+struct Validator;
+
+struct Unaddressed;
+
+impl FieldAddress for Unaddressed {
+    fn root() -> Self {
+        Self
+    }
+    fn child(&self, _number: u64) -> Self {
+        Self
+    }
+    fn unordered(&self) -> (Self, Self) {
+        (Self, Self)
+    }
+}
+
+impl StableHasher for Validator {
+    type Out = ();
+    type Addr = Unaddressed;
+    type Bytes = [u8; 0];
+
+    fn new() -> Self {
+        Self
+    }
+    fn write(&mut self, _field_address: Self::Addr, _bytes: &[u8]) {}
+    fn write_chunks<'a>(
+        &mut self,
+        _field_address: Self::Addr,
+        _chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) {
+    }
+    fn write_member(&mut self, _result_address: Self::Addr, _member: &Self) -> usize {
+        0
+    }
+    fn mixin(&mut self, _other: &Self) {}
+    fn finish(&self) -> Self::Out {}
+    fn to_bytes(&self) -> Self::Bytes {
+        []
+    }
+    fn from_bytes(_bytes: Self::Bytes) -> Self {
+        Self
+    }
+}
+
+struct Serializer<'a, H: StableHasher> {
+    field_address: H::Addr,
+    state: &'a mut H,
+}
+
+impl<'a, H: StableHasher> Serializer<'a, H> {
+    fn hash(self, value: impl StableHash) -> Result<(), Error> {
+        value.stable_hash(self.field_address, self.state);
+        Ok(())
+    }
+
+    fn variant(self, variant_index: u32) -> Variant<'a, H> {
+        Variant {
+            compound: Compound {
+                field_address: self.field_address.child(0),
+                state: self.state,
+                index: 0,
+            },
+            field_address: self.field_address,
+            variant_index,
+        }
+    }
+
+    fn compound(self) -> Compound<'a, H> {
+        Compound {
+            field_address: self.field_address,
+            state: self.state,
+            index: 0,
+        }
+    }
+}
+
+/// Fields of a struct or tuple, or items of a sequence.
+struct Compound<'a, H: StableHasher> {
+    field_address: H::Addr,
+    state: &'a mut H,
+    index: u64,
+}
+
+impl<H: StableHasher> Compound<'_, H> {
+    fn field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let serializer = Serializer {
+            field_address: self.field_address.child(self.index),
+            state: &mut *self.state,
+        };
+        self.index += 1;
+        value.serialize(serializer)
+    }
+}
+
+/// The payload of an enum variant, which is followed by the variant index.
+struct Variant<'a, H: StableHasher> {
+    compound: Compound<'a, H>,
+    field_address: H::Addr,
+    variant_index: u32,
+}
+
+impl<H: StableHasher> Variant<'_, H> {
+    fn end(self) -> Result<(), Error> {
+        self.variant_index
+            .stable_hash(self.field_address, self.compound.state);
+        Ok(())
+    }
+}
+
+/// Entries of a map. Each entry is hashed as a (key, value) tuple within
+/// an independent hasher, exactly as HashMap does. As with MapEntry, an entry
+/// whose value serializes as unit is hashed as only its key, so that a map to ()
+/// matches HashSet.
+/// See also a817fb02-7c77-41d6-98e4-dee123884287
+struct Map<'a, H: StableHasher> {
+    field_address: H::Addr,
+    state: &'a mut H,
+    entry: Option<PendingEntry>,
+}
+
+/// An entry whose key has been serialized, but not yet its value. Until the value
+/// shows whether it is unit, the address of the key is not known, so the key is
+/// recorded rather than hashed.
+struct PendingEntry {
+    key: KeyRecorder,
+}
+
+/// The writes made while serializing a key, at paths relative to the key. This
+/// allows the key to be serialized once, and hashed at its address once known.
+#[derive(Clone, Default)]
+struct KeyRecorder {
+    writes: Vec<(FieldPath, RecordedWrite)>,
+}
+
+#[derive(Clone)]
+enum RecordedWrite {
+    Field(Vec<u8>),
+    Member(KeyRecorder),
+}
+
+impl KeyRecorder {
+    /// Hashes the recorded writes as if the key had been serialized at the address
+    fn replay<H: StableHasher>(&self, key_address: &impl Fn() -> H::Addr, state: &mut H) {
+        for (path, write) in &self.writes {
+            let mut field_address = key_address();
+            for item in &path.0 {
+                field_address = match item {
+                    PathItem::Child(number) => field_address.child(*number),
+                    PathItem::UnorderedRoot => field_address.unordered().0,
+                    PathItem::UnorderedResult => field_address.unordered().1,
+                };
+            }
+            match write {
+                RecordedWrite::Field(bytes) => state.write(field_address, bytes),
+                RecordedWrite::Member(recorded) => {
+                    // Member paths are also relative to the key
+                    let mut member = H::new();
+                    recorded.replay(key_address, &mut member);
+                    state.write_member(field_address, &member);
+                }
+            }
+        }
+    }
+}
+
+impl StableHasher for KeyRecorder {
+    type Out = ();
+    type Addr = FieldPath;
+    type Bytes = Vec<u8>;
+
+    fn new() -> Self {
+        Self::default()
+    }
+    fn write(&mut self, field_address: Self::Addr, bytes: &[u8]) {
+        self.writes
+            .push((field_address, RecordedWrite::Field(bytes.to_vec())));
+    }
+    fn write_chunks<'a>(
+        &mut self,
+        field_address: Self::Addr,
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) {
+        let mut bytes = Vec::new();
+        for chunk in chunks {
+            bytes.extend_from_slice(chunk);
+        }
+        self.writes
+            .push((field_address, RecordedWrite::Field(bytes)));
+    }
+    fn write_member(&mut self, result_address: Self::Addr, member: &Self) -> usize {
+        self.writes
+            .push((result_address, RecordedWrite::Member(member.clone())));
+        0
+    }
+    fn mixin(&mut self, other: &Self) {
+        self.writes.extend(other.writes.iter().cloned());
+    }
+    fn finish(&self) -> Self::Out {}
+    fn to_bytes(&self) -> Self::Bytes {
+        unimplemented!("A recorded key is replayed rather than serialized")
+    }
+    fn from_bytes(_bytes: Self::Bytes) -> Self {
+        unimplemented!("A recorded key is replayed rather than serialized")
+    }
+}
+
+impl<'a, H: StableHasher> ser::Serializer for Serializer<'a, H> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'a, H>;
+    type SerializeTuple = Compound<'a, H>;
+    type SerializeTupleStruct = Compound<'a, H>;
+    type SerializeTupleVariant = Variant<'a, H>;
+    type SerializeMap = Map<'a, H>;
+    type SerializeStruct = Compound<'a, H>;
+    type SerializeStructVariant = Variant<'a, H>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.hash(v)
+    }
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.hash(v)
+    }
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.hash(v)
+    }
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.hash(v)
+    }
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.hash(v)
+    }
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        self.hash(v)
+    }
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.hash(v)
+    }
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.hash(v)
+    }
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.hash(v)
+    }
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.hash(v)
+    }
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        self.hash(v)
+    }
+    fn serialize_f32(self, _v: f32) -> Result<(), Error> {
+        Err(ser::Error::custom("f32 is not supported"))
+    }
+    fn serialize_f64(self, _v: f64) -> Result<(), Error> {
+        Err(ser::Error::custom("f64 is not supported"))
+    }
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        // Same as a string containing only this char
+        self.hash(v.encode_utf8(&mut [0; 4]) as &str)
+    }
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.hash(v)
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        self.hash(AsBytes(v))
+    }
+    fn serialize_none(self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), Error> {
+        // Same as Option
+        value.serialize(Serializer {
+            field_address: self.field_address.child(0),
+            state: &mut *self.state,
+        })?;
+        self.state.write(self.field_address, &[]);
+        Ok(())
+    }
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error> {
+        self.hash(variant_index)
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(Serializer {
+            field_address: self.field_address.child(0),
+            state: &mut *self.state,
+        })?;
+        self.hash(variant_index)
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a, H>, Error> {
+        Ok(self.compound())
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Compound<'a, H>, Error> {
+        Ok(self.compound())
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, H>, Error> {
+        Ok(self.compound())
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Variant<'a, H>, Error> {
+        Ok(self.variant(variant_index))
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Map<'a, H>, Error> {
+        Ok(Map {
+            field_address: self.field_address,
+            state: self.state,
+            entry: None,
+        })
+    }
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Compound<'a, H>, Error> {
+        Ok(self.compound())
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Variant<'a, H>, Error> {
+        Ok(self.variant(variant_index))
+    }
+}
+
+impl<H: StableHasher> ser::SerializeSeq for Compound<'_, H> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.field(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        // Same as a slice
+        // See also 33a9b3bf-0d43-4fd0-a3ed-a77807505255
+        self.index.stable_hash(self.field_address, self.state);
+        Ok(())
+    }
+}
+
+impl<H: StableHasher> ser::SerializeTuple for Compound<'_, H> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.field(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<H: StableHasher> ser::SerializeTupleStruct for Compound<'_, H> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.field(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<H: StableHasher> ser::SerializeStruct for Compound<'_, H> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(value)
+    }
+    fn skip_field(&mut self, _key: &'static str) -> Result<(), Error> {
+        // Skipped fields keep their index so that later fields do not move
+        self.index += 1;
+        Ok(())
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<H: StableHasher> ser::SerializeTupleVariant for Variant<'_, H> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.compound.field(value)
+    }
+    fn end(self) -> Result<(), Error> {
+        Variant::end(self)
+    }
+}
+
+impl<H: StableHasher> ser::SerializeStructVariant for Variant<'_, H> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.compound.field(value)
+    }
+    fn skip_field(&mut self, _key: &'static str) -> Result<(), Error> {
+        self.compound.index += 1;
+        Ok(())
+    }
+    fn end(self) -> Result<(), Error> {
+        Variant::end(self)
+    }
+}
+
+impl<H: StableHasher> ser::SerializeMap for Map<'_, H> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        let mut recorder = KeyRecorder::new();
+        key.serialize(Serializer {
+            field_address: FieldPath::root(),
+            state: &mut recorder,
+        })?;
+        self.entry = Some(PendingEntry { key: recorder });
+        Ok(())
+    }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let entry = self
+            .entry
+            .take()
+            .ok_or_else(|| ser::Error::custom("serialize_value called before serialize_key"))?;
+        let field_address = &self.field_address;
+        let mut new_hasher = H::new();
+        if is_unit(value) {
+            entry
+                .key
+                .replay(&|| field_address.unordered().0, &mut new_hasher);
+        } else {
+            entry
+                .key
+                .replay(&|| field_address.unordered().0.child(0), &mut new_hasher);
+            value.serialize(Serializer {
+                field_address: field_address.unordered().0.child(1),
+                state: &mut new_hasher,
+            })?;
+        }
+        self.state
+            .write_member(field_address.unordered().1, &new_hasher);
+        Ok(())
+    }
+    fn serialize_entry<K: ?Sized + Serialize, V: ?Sized + Serialize>(
+        &mut self,
+        key: &K,
+        value: &V,
+    ) -> Result<(), Error> {
+        let (a, b) = self.field_address.unordered();
+        let mut new_hasher = H::new();
+        if is_unit(value) {
+            key.serialize(Serializer {
+                field_address: a,
+                state: &mut new_hasher,
+            })?;
+        } else {
+            key.serialize(Serializer {
+                field_address: a.child(0),
+                state: &mut new_hasher,
+            })?;
+            value.serialize(Serializer {
+                field_address: a.child(1),
+                state: &mut new_hasher,
+            })?;
+        }
+        self.state.write_member(b, &new_hasher);
+        Ok(())
+    }
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Whether the value serializes as (), the serde equivalent of StableHash::IS_UNIT
+fn is_unit<T: ?Sized + Serialize>(value: &T) -> bool {
+    matches!(value.serialize(UnitProbe), Ok(true))
+}
+
+/// Answers whether a value is unit from the first call made by its Serialize impl.
+/// Anything other than serialize_unit is not unit, and compound values fail
+/// before serializing any of their contents.
+struct UnitProbe;
+
+macro_rules! not_unit {
+    ($($method:ident($($arg:ty),*);)*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<bool, Error> {
+                Ok(false)
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for UnitProbe {
+    type Ok = bool;
+    type Error = Error;
+    type SerializeSeq = ser::Impossible<bool, Error>;
+    type SerializeTuple = ser::Impossible<bool, Error>;
+    type SerializeTupleStruct = ser::Impossible<bool, Error>;
+    type SerializeTupleVariant = ser::Impossible<bool, Error>;
+    type SerializeMap = ser::Impossible<bool, Error>;
+    type SerializeStruct = ser::Impossible<bool, Error>;
+    type SerializeStructVariant = ser::Impossible<bool, Error>;
+
+    not_unit! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_i128(i128);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_u128(u128);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_char(char);
+        serialize_str(&str);
+        serialize_bytes(&[u8]);
+        serialize_none();
+        serialize_unit_struct(&'static str);
+        serialize_unit_variant(&'static str, u32, &'static str);
+    }
+    fn serialize_unit(self) -> Result<bool, Error> {
+        Ok(true)
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, _: &T) -> Result<bool, Error> {
+        Ok(false)
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<bool, Error> {
+        Ok(false)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<bool, Error> {
+        Ok(false)
+    }
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(not_unit())
+    }
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(not_unit())
+    }
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(not_unit())
+    }
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(not_unit())
+    }
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(not_unit())
+    }
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Error> {
+        Err(not_unit())
+    }
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(not_unit())
+    }
+}
+
+fn not_unit() -> Error {
+    Error("not unit".to_string())
+}
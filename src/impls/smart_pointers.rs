@@ -0,0 +1,45 @@
+use crate::prelude::*;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+
+// Smart pointers are transparent, so eg: Box<T> hashes exactly like T. This means
+// that defaults are skipped by the pointee, and Option<Box<T>> behaves like
+// Option<T> with Some(Box::new(default)) being distinct from None.
+
+macro_rules! impl_transparent_pointer {
+    ($($P:ident),*) => {
+        $(
+            impl<T: StableHash> StableHash for $P<T> {
+                const TYPE_ID: Option<u64> = T::TYPE_ID;
+                const IS_UNIT: bool = T::IS_UNIT;
+
+                #[inline]
+                fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+                    profile_method!(stable_hash);
+
+                    (**self).stable_hash(field_address, state)
+                }
+            }
+        )*
+    }
+}
+
+impl_transparent_pointer!(Box, Rc, Arc);
+
+/// Transparent like the pointer it wraps, so eg: Pin<Box<T>> hashes exactly like T.
+impl<P: Deref> StableHash for Pin<P>
+where
+    P::Target: StableHash,
+{
+    const TYPE_ID: Option<u64> = <P::Target as StableHash>::TYPE_ID;
+    const IS_UNIT: bool = <P::Target as StableHash>::IS_UNIT;
+
+    #[inline]
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        (**self).stable_hash(field_address, state)
+    }
+}
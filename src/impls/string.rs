@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use std::ffi::{OsStr, OsString};
 
 impl StableHash for String {
     fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
@@ -8,10 +9,40 @@ impl StableHash for String {
     }
 }
 
-impl<'a> StableHash for &'a str {
+/// Hashes the UTF-8 bytes exactly as they are. No Unicode normalization is done,
+/// so strings which are canonically equivalent but encoded differently (eg: "é"
+/// as one code point, or as "e" followed by a combining accent) do not collide.
+/// See also NormalizedStr (behind the unicode-normalization feature).
+impl StableHash for &str {
     fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
         profile_method!(stable_hash);
 
         AsBytes(self.as_bytes()).stable_hash(field_address, state)
     }
 }
+
+impl StableHash for OsString {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        self.as_os_str().stable_hash(field_address, state);
+    }
+}
+
+/// A string which is valid Unicode hashes exactly like the same &str, on every
+/// platform, even though the native encoding differs (eg: UTF-16 on Windows).
+/// So, eg: a HashMap<OsString, V> with Unicode keys hashes the same as a
+/// HashMap<String, V>.
+///
+/// Otherwise, the platform specific encoded bytes are hashed. A string which is
+/// not valid Unicode can only have come from the platform (eg: arbitrary bytes on
+/// Unix or unpaired surrogates on Windows), so its hash is not stable across
+/// platforms. Convert such strings explicitly if that matters.
+impl StableHash for &OsStr {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        // For valid Unicode, the encoded bytes are exactly the UTF-8 bytes
+        AsBytes(self.as_encoded_bytes()).stable_hash(field_address, state)
+    }
+}
@@ -0,0 +1,37 @@
+use crate::prelude::*;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Hashed like a struct { secs, nanos } where nanos is the sub-second part.
+/// So, a zero Duration is the default.
+///
+/// Duration only has nanosecond precision, so any finer precision (eg: of a
+/// value measured in picoseconds) is lost when the Duration is created, and
+/// values which round to the same nanosecond hash the same. The two parts are
+/// hashed separately rather than as a total number of nanoseconds, which would
+/// overflow a u64, so every Duration up to Duration::MAX is hashed exactly.
+impl StableHash for Duration {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        self.as_secs().stable_hash(field_address.child(0), state);
+        self.subsec_nanos()
+            .stable_hash(field_address.child(1), state);
+    }
+}
+
+/// Hashed like a struct { magnitude, before_epoch } where magnitude is the
+/// Duration between the time and UNIX_EPOCH. The explicit sign means that times
+/// the same distance before and after the epoch do not collide. UNIX_EPOCH
+/// itself is the default.
+impl StableHash for SystemTime {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        let (magnitude, before_epoch) = match self.duration_since(UNIX_EPOCH) {
+            Ok(magnitude) => (magnitude, false),
+            Err(err) => (err.duration(), true),
+        };
+        magnitude.stable_hash(field_address.child(0), state);
+        before_epoch.stable_hash(field_address.child(1), state);
+    }
+}
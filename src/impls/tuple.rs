@@ -1,5 +1,19 @@
 use crate::prelude::*;
 
+/// The unit type carries no information, so is always the default.
+impl StableHash for () {
+    const IS_UNIT: bool = true;
+
+    fn stable_hash<H: StableHasher>(&self, _field_address: H::Addr, _state: &mut H) {
+        profile_method!(stable_hash);
+    }
+}
+
+// Each element is written at child(index), exactly like the fields of a struct
+// declared with impl_stable_hash!. So a tuple hashes like a struct with the same
+// fields in the same order, and since default values write nothing, appending
+// default elements to a tuple does not change its hash. eg: (a,), (a, 0), and
+// One { one: a } all hash the same.
 macro_rules! impl_tuple {
     ($($T:ident),*) => {
         impl<$($T : StableHash,)*> StableHash for ($($T,)*) {
@@ -22,7 +36,9 @@ macro_rules! impl_tuple {
 }
 
 macro_rules! impl_tuples {
-    ($T:ident) => { };
+    ($T:ident) => {
+        impl_tuple!($T);
+    };
     ($Head:ident, $($Tail:ident),+) => {
         impl_tuple!($Head, $($Tail),+);
         impl_tuples!($($Tail),+);
@@ -0,0 +1,24 @@
+use crate::prelude::*;
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization as _};
+
+/// Hashes a string after converting it to Unicode Normalization Form C (NFC),
+/// so that canonically equivalent strings hash the same. For example, "é" as a
+/// single code point and as "e" followed by a combining accent do not differ.
+///
+/// A string which is already in NFC hashes the same as the plain &str, so a field
+/// may be changed to use this wrapper without changing the hash of such values.
+pub struct NormalizedStr<'a>(pub &'a str);
+
+impl StableHash for NormalizedStr<'_> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        // Most strings are already normalized, which can be detected without allocating
+        if is_nfc_quick(self.0.chars()) == IsNormalized::Yes {
+            self.0.stable_hash(field_address, state)
+        } else {
+            let normalized: String = self.0.nfc().collect();
+            normalized.stable_hash(field_address, state)
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use std::collections::VecDeque;
 
 impl<T: StableHash> StableHash for Vec<T> {
     fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
@@ -8,17 +9,75 @@ impl<T: StableHash> StableHash for Vec<T> {
     }
 }
 
-impl<'a, T: StableHash> StableHash for &'a [T] {
+impl<T: StableHash> OrderedCollection for [T] {
+    fn items(&self) -> impl Iterator<Item = impl StableHash + '_> + '_ {
+        self.iter()
+    }
+}
+
+impl<T: StableHash> StableHash for &[T] {
     fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
         profile_method!(stable_hash);
 
-        for (index, item) in self.iter().enumerate() {
-            item.stable_hash(field_address.child(index as u64), state);
-        }
-        // This is needed to disambiguate when the last members are default
-        // For example, vec![true, false] and vec![true, false, false] should
-        // not collide.
-        // See also 33a9b3bf-0d43-4fd0-a3ed-a77807505255
-        self.len().stable_hash(field_address, state);
+        self.stable_hash_ordered(field_address, state)
+    }
+}
+
+impl<T: StableHash> StableHash for Box<[T]> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        (&self[..]).stable_hash(field_address, state)
+    }
+}
+
+/// Hashed exactly like Vec, so the length is written even though it is part of the type.
+/// This means that [T; N], &[T; N], &[T] and Vec<T> with the same items all agree.
+impl<T: StableHash, const N: usize> StableHash for [T; N] {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        (&self[..]).stable_hash(field_address, state)
+    }
+}
+
+/// Hashed exactly like Vec, so the order of the items matters. This means that
+/// rotating a VecDeque changes its hash, even though it has the same items.
+/// For a hash which does not depend on order, use an unordered collection
+/// such as HashSet or BinaryHeap instead.
+impl<T: StableHash> StableHash for VecDeque<T> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        self.stable_hash_ordered(field_address, state)
+    }
+}
+
+impl<T: StableHash> OrderedCollection for VecDeque<T> {
+    fn items(&self) -> impl Iterator<Item = impl StableHash + '_> + '_ {
+        self.iter()
+    }
+}
+
+pub(crate) fn sequence_stable_hash<H: StableHasher>(
+    items: impl Iterator<Item = impl StableHash>,
+    field_address: H::Addr,
+    state: &mut H,
+) {
+    // Counted as u64 rather than usize so that the encoding of the length
+    // does not depend on the platform.
+    let mut len = 0u64;
+    for item in items {
+        item.stable_hash(field_address.child(len), state);
+        len += 1;
     }
+    // This is needed to disambiguate when the last members are default
+    // For example, vec![true, false] and vec![true, false, false] should
+    // not collide.
+    //
+    // The length is written exactly once, after the single pass over the items,
+    // and always at the parent address. Every item is written at a child address,
+    // so the length cannot collide with an item or with a field of an item.
+    // See also 33a9b3bf-0d43-4fd0-a3ed-a77807505255
+    len.stable_hash(field_address, state);
 }
@@ -0,0 +1,36 @@
+use crate::prelude::*;
+use std::{rc, sync};
+
+// A live weak reference hashes exactly like the value it points to, and a dead
+// one is the default and writes nothing.
+//
+// Note that this means the hash of a value holding a Weak can change without the
+// value itself being modified, if the last strong reference to the target is
+// dropped in the meantime. Hash such values only while their targets are known
+// to be alive, or when a dangling reference is meant to be equivalent to none.
+
+impl<T: StableHash> StableHash for rc::Weak<T> {
+    const TYPE_ID: Option<u64> = T::TYPE_ID;
+    const IS_UNIT: bool = T::IS_UNIT;
+
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        if let Some(value) = self.upgrade() {
+            value.stable_hash(field_address, state);
+        }
+    }
+}
+
+impl<T: StableHash> StableHash for sync::Weak<T> {
+    const TYPE_ID: Option<u64> = T::TYPE_ID;
+    const IS_UNIT: bool = T::IS_UNIT;
+
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        if let Some(value) = self.upgrade() {
+            value.stable_hash(field_address, state);
+        }
+    }
+}
@@ -14,15 +14,23 @@
 //!    (where collide is defined as contribution to the hash is injective in respect to the encoding. It is
 //!    still possible to find collisions in the final output, especially for the non-cryptographic version)
 
+mod content_id;
 pub mod crypto;
+#[cfg(feature = "debug")]
+pub mod debug;
 pub mod fast;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 mod impls;
 mod macros;
 pub mod prelude;
+pub mod sip;
 pub mod utils;
 mod verification;
 use prelude::*;
 
+pub use content_id::{content_id, ContentId, ParseContentIdError};
+
 /// Like Hasher, but consistent across:
 /// * builds (independent of rustc version or std implementation details)
 /// * platforms (eg: 32 bit & 64 bit, x68 and ARM)
@@ -40,6 +48,33 @@ pub trait StableHasher {
     /// Add a single field to the hash
     fn write(&mut self, field_address: Self::Addr, bytes: &[u8]);
 
+    /// Add many fields to the hash. This is equivalent to calling write
+    /// for each field in order, but hashers may override it to amortize the
+    /// fixed cost of each call. Takes an iterator rather than a slice because
+    /// field addresses are not required to be Clone.
+    fn write_many<'a>(&mut self, fields: impl IntoIterator<Item = (Self::Addr, &'a [u8])>) {
+        for (field_address, bytes) in fields {
+            self.write(field_address, bytes);
+        }
+    }
+
+    /// Add a single field whose payload is split into chunks. This is equivalent
+    /// to calling write with the chunks concatenated, regardless of where the
+    /// payload is split. The default concatenates them, and hashers may override
+    /// it to consume the chunks as they go, so that the whole payload is never
+    /// held in memory at once.
+    fn write_chunks<'a>(
+        &mut self,
+        field_address: Self::Addr,
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) {
+        let mut bytes = Vec::new();
+        for chunk in chunks {
+            bytes.extend_from_slice(chunk);
+        }
+        self.write(field_address, &bytes);
+    }
+
     /// Adds all fields from another hasher
     fn mixin(&mut self, other: &Self);
 
@@ -48,16 +83,33 @@ pub trait StableHasher {
         unimplemented!()
     }
 
+    /// Writes the state of the hasher for a single member of an unordered
+    /// collection at the result address of the collection. Returns the length
+    /// of the payload written, so that it is not serialized again to count it.
+    #[doc(hidden)]
+    fn write_member(&mut self, result_address: Self::Addr, member: &Self) -> usize
+    where
+        Self: Sized,
+    {
+        let bytes = member.to_bytes();
+        self.write(result_address, bytes.as_ref());
+        bytes.as_ref().len()
+    }
+
     /// Finalize the digest
     fn finish(&self) -> Self::Out;
 
     /// Used when serializing
     type Bytes: AsRef<[u8]>;
 
-    /// Serialize
+    /// Serialize the complete state of the hasher (not the finished digest).
+    /// This representation is stable, so it may be persisted or sent to another
+    /// process or machine.
     fn to_bytes(&self) -> Self::Bytes;
 
-    /// Deserialize
+    /// Deserialize. The result is indistinguishable from the hasher that was
+    /// serialized, so it may continue to be written to, mixed in, or unmixed
+    /// from other hashers, and finishes with the same output.
     fn from_bytes(bytes: Self::Bytes) -> Self;
 }
 
@@ -69,6 +121,26 @@ pub trait StableHasher {
 /// For examples of best practices when implementing:
 /// See also d3ba3adc-6e9b-4586-a7e7-6b542df39462
 pub trait StableHash {
+    /// An optional identifier for the type, which prevents a value of this type from
+    /// colliding with a value of an unrelated type that happens to have the same layout.
+    ///
+    /// When set, a value hashed at the root (eg: by fast_stable_hash) is hashed at
+    /// root().child(TYPE_ID) instead. Since that changes every hash of the type,
+    /// setting it is not backward compatible with values hashed before it was set.
+    /// It also means the type no longer hashes the same as other types with the
+    /// same layout, which gives up eg: changing a field from u32 to u64. Choose a
+    /// random u64, rather than a small number which may be used by a field.
+    ///
+    /// Only the type of the root value is considered. A value nested inside another
+    /// hashes the same regardless of its TYPE_ID.
+    const TYPE_ID: Option<u64> = None;
+
+    /// Whether this is (). A map to () is hashed as the set of its keys.
+    /// Only () sets this, and wrappers which are transparent (eg: &T and Box<T>)
+    /// forward it along with TYPE_ID.
+    #[doc(hidden)]
+    const IS_UNIT: bool = false;
+
     fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H);
 }
 
@@ -116,6 +188,16 @@ pub trait FieldAddress: Sized {
     /// To maintain backward compatibility, this number should remain consistent
     /// even as fields are added, removed, or re-ordered.
     fn child(&self, number: u64) -> Self;
+    /// Like child, but the field is identified by a stable name rather than
+    /// a number. Fields identified this way may be re-ordered without changing
+    /// the hash, as long as the names do not change.
+    ///
+    /// The default derives a number in the range 2^62..2^63 from a hash of the name,
+    /// so it does not collide with the small numbers used for positional fields.
+    fn named_child(&self, name: &str) -> Self {
+        let hash = xxhash_rust::xxh3::xxh3_64(name.as_bytes());
+        self.child((1 << 62) | (hash >> 2))
+    }
     /// This one is tricky, as it involves hashing a set online.
     /// In this case, each member of the set has the same FieldAddress, but work must
     /// be done to relate multiple field addresses within the same set. The first return
@@ -149,11 +231,87 @@ pub fn fast_stable_hash<T: StableHash>(value: &T) -> u128 {
     generic_stable_hash::<T, crate::fast::FastStableHasher>(value)
 }
 
+/// Known collision: a member of an unordered collection which writes nothing
+/// (eg: 0, "", or None) is not distinguished from the collection's own fields.
+/// So HashSet {0u32}, vec![0u32] and 1u32 all hash the same. Fixing it would
+/// change hashes which have already been persisted, so it waits for the next
+/// breaking version. crypto_stable_hash_v2 (with the unstable crypto-v2 feature)
+/// does not have it.
 pub fn crypto_stable_hash<T: StableHash>(value: &T) -> [u8; 32] {
     profile_fn!(crypto_stable_hash);
     generic_stable_hash::<T, crate::crypto::CryptoStableHasher>(value)
 }
 
+/// Like crypto_stable_hash, but with CryptoStableHasherV2. The output is the same
+/// as crypto_stable_hash, except for values which contain an unordered collection
+/// with a member which writes nothing (eg: HashSet {0u32}). See
+/// CryptoStableHasherV2 for how those are written.
+///
+/// This is behind the crypto-v2 feature, and is unstable until its encoding is
+/// specified. Its output may change, so it should not yet be persisted.
+#[cfg(feature = "crypto-v2")]
+pub fn crypto_stable_hash_v2<T: StableHash>(value: &T) -> [u8; 32] {
+    profile_fn!(crypto_stable_hash_v2);
+    generic_stable_hash::<T, crate::crypto::CryptoStableHasherV2>(value)
+}
+
+/// Selects which backend is used by [stable_hash], trading speed for
+/// collision resistance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HashProfile {
+    /// Uses [fast::FastStableHasher]. Suitable for cache keys and other
+    /// cases where a rare collision in the final output is acceptable.
+    Fast,
+    /// Uses [crypto::CryptoStableHasher]. Suitable for consensus-critical values.
+    Crypto,
+}
+
+/// The digest produced by [stable_hash]. The variant always matches
+/// the [HashProfile] that was requested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HashOutput {
+    Fast(u128),
+    Crypto([u8; 32]),
+}
+
+/// Hashes the value using the backend selected by the profile. This allows
+/// a subsystem to choose its profile in one place rather than hardcoding
+/// the backend at each call site.
+pub fn stable_hash<T: StableHash>(value: &T, profile: HashProfile) -> HashOutput {
+    profile_fn!(stable_hash);
+    match profile {
+        HashProfile::Fast => HashOutput::Fast(fast_stable_hash(value)),
+        HashProfile::Crypto => HashOutput::Crypto(crypto_stable_hash(value)),
+    }
+}
+
+/// A fast hash along with the version of the schema which produced it.
+/// See [fast_stable_hash_versioned].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VersionedHash {
+    pub schema_version: u32,
+    pub value: u128,
+}
+
+/// Hashes the value with the version of its schema mixed in. When the StableHash
+/// impl of a type is deliberately changed, bump the version so that hashes stored
+/// under the old schema can be detected as stale rather than silently mismatching.
+///
+/// The value is hashed as the tuple (value, schema_version), so the result differs
+/// from fast_stable_hash of the value even when the version is 0.
+pub fn fast_stable_hash_versioned<T: StableHash>(value: &T, schema_version: u32) -> VersionedHash {
+    profile_fn!(fast_stable_hash_versioned);
+    VersionedHash {
+        schema_version,
+        value: fast_stable_hash(&(value, schema_version)),
+    }
+}
+
+/// The version of the schema which produced the hash.
+pub fn schema_version_of(hash: &VersionedHash) -> u32 {
+    hash.schema_version
+}
+
 #[cfg(test)]
 mod tests {
     use std::fmt::Debug;
@@ -163,7 +321,7 @@ mod tests {
 
     use crate::crypto::CryptoStableHasher;
     use crate::fast::FastStableHasher;
-    use crate::StableHasher;
+    use crate::{FieldAddress, StableHasher};
 
     #[test]
     fn unmix_fast() {
@@ -175,6 +333,174 @@ mod tests {
         unmix_fuzz(30, CryptoStableHasher::rand);
     }
 
+    #[test]
+    fn write_many_fast() {
+        write_many_matches_write::<FastStableHasher>();
+    }
+
+    #[test]
+    fn write_many_crypto() {
+        write_many_matches_write::<CryptoStableHasher>();
+    }
+
+    fn write_many_matches_write<T>()
+    where
+        T: StableHasher + Eq + Debug,
+    {
+        let payloads: Vec<Vec<u8>> = (0..20u8).map(|i| vec![i; i as usize]).collect();
+        let root = T::Addr::root();
+
+        let mut sequential = T::new();
+        for (i, payload) in payloads.iter().enumerate() {
+            sequential.write(root.child(i as u64), payload);
+        }
+
+        let mut batch = T::new();
+        batch.write_many(
+            payloads
+                .iter()
+                .enumerate()
+                .map(|(i, payload)| (root.child(i as u64), &payload[..])),
+        );
+
+        assert_eq!(sequential, batch);
+        assert_eq!(sequential.to_bytes().as_ref(), batch.to_bytes().as_ref());
+    }
+
+    fn write_chunks_matches_write<T>()
+    where
+        T: StableHasher + Eq + Debug,
+    {
+        let payload: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let field_address = T::Addr::root().child(3);
+
+        let mut whole = T::new();
+        whole.write(field_address.child(0), &payload);
+
+        for chunk_size in [1, 7, 16, 64, 1000, 4096, 10_000, 20_000] {
+            let mut chunked = T::new();
+            chunked.write_chunks(field_address.child(0), payload.chunks(chunk_size));
+            assert_eq!(whole, chunked);
+        }
+
+        // Empty chunks do not affect the result
+        let (left, right) = payload.split_at(5000);
+        let mut chunked = T::new();
+        chunked.write_chunks(field_address.child(0), [&[][..], left, &[], right, &[]]);
+        assert_eq!(whole, chunked);
+    }
+
+    #[test]
+    fn write_chunks_fast() {
+        write_chunks_matches_write::<FastStableHasher>();
+    }
+
+    #[test]
+    fn write_chunks_crypto() {
+        write_chunks_matches_write::<CryptoStableHasher>();
+    }
+
+    #[test]
+    fn write_chunks_sip() {
+        write_chunks_matches_write::<crate::sip::SipStableHasher>();
+    }
+
+    /// Delegates to T, except that write_chunks is left as the default
+    #[derive(PartialEq, Eq, Debug)]
+    struct DefaultChunks<T>(T);
+
+    impl<T: StableHasher> StableHasher for DefaultChunks<T> {
+        type Out = T::Out;
+        type Addr = T::Addr;
+        type Bytes = T::Bytes;
+
+        fn new() -> Self {
+            Self(T::new())
+        }
+        fn write(&mut self, field_address: Self::Addr, bytes: &[u8]) {
+            self.0.write(field_address, bytes)
+        }
+        fn mixin(&mut self, other: &Self) {
+            self.0.mixin(&other.0)
+        }
+        fn finish(&self) -> Self::Out {
+            self.0.finish()
+        }
+        fn to_bytes(&self) -> Self::Bytes {
+            self.0.to_bytes()
+        }
+        fn from_bytes(bytes: Self::Bytes) -> Self {
+            Self(T::from_bytes(bytes))
+        }
+    }
+
+    fn default_write_chunks_matches_override<T>()
+    where
+        T: StableHasher + Eq + Debug,
+    {
+        write_chunks_matches_write::<DefaultChunks<T>>();
+
+        let payload: Vec<u8> = (0..10_000u32).map(|i| (i % 13) as u8).collect();
+        let field_address = || T::Addr::root().child(1);
+        for chunk_size in [1, 64, 10_000] {
+            let mut default = DefaultChunks::<T>::new();
+            default.write_chunks(field_address(), payload.chunks(chunk_size));
+            let mut overridden = T::new();
+            overridden.write_chunks(field_address(), payload.chunks(chunk_size));
+            assert_eq!(default.0, overridden);
+        }
+    }
+
+    #[test]
+    fn default_write_chunks_fast() {
+        default_write_chunks_matches_override::<FastStableHasher>();
+    }
+
+    #[test]
+    fn default_write_chunks_crypto() {
+        default_write_chunks_matches_override::<CryptoStableHasher>();
+    }
+
+    #[test]
+    fn default_write_chunks_sip() {
+        default_write_chunks_matches_override::<crate::sip::SipStableHasher>();
+    }
+
+    #[test]
+    fn round_trip_fast() {
+        round_trip_then_mix(FastStableHasher::rand);
+    }
+
+    #[test]
+    fn round_trip_crypto() {
+        round_trip_then_mix(CryptoStableHasher::rand);
+    }
+
+    fn round_trip_then_mix<T, F>(f: F)
+    where
+        F: Fn() -> T,
+        T: StableHasher + Eq + Debug + Clone,
+        T::Out: Eq + Debug,
+    {
+        let a = f();
+        let b = f();
+        let round_trip = |h: &T| T::from_bytes(h.to_bytes());
+
+        let mut mixed = a.clone();
+        mixed.mixin(&b);
+
+        let mut restored = round_trip(&a);
+        restored.mixin(&round_trip(&b));
+        assert_eq!(mixed, restored);
+        assert_eq!(mixed.finish(), restored.finish());
+
+        // The combined state also survives a round trip and can be unmixed
+        let mut restored = round_trip(&restored);
+        restored.unmix(&round_trip(&b));
+        assert_eq!(a, restored);
+        assert_eq!(a.finish(), restored.finish());
+    }
+
     fn unmix_fuzz<T, F>(count: u32, f: F)
     where
         F: Fn() -> T,
@@ -188,7 +514,7 @@ mod tests {
             v
         };
         let take_rand = |v: &mut Vec<T>| {
-            if v.len() == 0 {
+            if v.is_empty() {
                 return None;
             }
             let i = rng().gen_range(0..v.len());
@@ -2,12 +2,16 @@
 /// Struct { field1, field2, ... } and Tuple(transparent). Each field supports
 /// an optional modifier. For example: Tuple(transparent: AsBytes)
 ///
+/// Tuple(transparent) hashes the field at the address of the tuple, but does
+/// not take its TYPE_ID. To hash exactly like the field, including its TYPE_ID,
+/// give its type instead: Tuple(transparent Inner).
+///
 /// This API is unstable and will likely be modified for a 1.0 release.
 /// It's just a stub to cover some common cases.
 #[macro_export]
 macro_rules! impl_stable_hash {
     ($T:ident$(<$lt:lifetime>)? {$($field:ident$(:$e:path)?),*}) => {
-        impl $crate::StableHash for $T$(<$lt>)? {
+        impl$(<$lt>)? $crate::StableHash for $T$(<$lt>)? {
             // This suppressed warning is for the final index + 1, which is unused
             // in the next "iteration of the loop"
             #[allow(unused_assignments)]
@@ -27,7 +31,7 @@ macro_rules! impl_stable_hash {
         }
     };
     ($T:ident$(<$lt:lifetime>)? (transparent$(:$e:path)?)) => {
-        impl $crate::StableHash for $T$(<$lt>)? {
+        impl$(<$lt>)? $crate::StableHash for $T$(<$lt>)? {
             #[allow(unused_assignments)]
             fn stable_hash<H: $crate::StableHasher>(&self, field_address: H::Addr, state: &mut H) {
                 let Self(transparent) = self;
@@ -36,4 +40,342 @@ macro_rules! impl_stable_hash {
             }
         }
     };
+    ($T:ident$(<$lt:lifetime>)? (transparent $Inner:ty)) => {
+        impl$(<$lt>)? $crate::StableHash for $T$(<$lt>)? {
+            const TYPE_ID: Option<u64> = <$Inner as $crate::StableHash>::TYPE_ID;
+            const IS_UNIT: bool = <$Inner as $crate::StableHash>::IS_UNIT;
+
+            fn stable_hash<H: $crate::StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+                let Self(transparent) = self;
+                <$Inner as $crate::StableHash>::stable_hash(transparent, field_address, state);
+            }
+        }
+    };
+}
+
+/// Implements StableHash for a newtype by delegating to its single field, so that
+/// the newtype hashes exactly like the inner value. For example, given
+/// `struct NonEmptyString(String)`, `impl_stable_hash_transparent!(NonEmptyString)`
+/// makes `NonEmptyString("a".to_owned())` hash the same as `"a".to_owned()`.
+///
+/// The type of the field may also be given, as it is declared, eg:
+/// `impl_stable_hash_transparent!(NonEmptyString(String))`. The newtype then
+/// takes the TYPE_ID of the inner type as well, so the two also hash the same
+/// at the root when the inner type has one.
+///
+/// This differs from a struct with one field, which writes the field at child(0)
+/// and therefore does not hash the same as the bare field. Use this form for
+/// validated wrappers which should be interchangeable with the inner type.
+///
+/// This is shorthand for `impl_stable_hash!(NonEmptyString(transparent))`, or
+/// `impl_stable_hash!(NonEmptyString(transparent String))` with the type.
+///
+/// Newtypes which derive Deref and From (eg: with derive_more) should usually
+/// use this, so that wrapping a value does not change its hash:
+///
+/// ```
+/// # use stable_hash::{fast_stable_hash, impl_stable_hash_transparent};
+/// // Typically #[derive(Deref, From)]
+/// struct UserId(u64);
+///
+/// impl std::ops::Deref for UserId {
+///     type Target = u64;
+///     fn deref(&self) -> &u64 {
+///         &self.0
+///     }
+/// }
+///
+/// impl_stable_hash_transparent!(UserId);
+///
+/// assert_eq!(fast_stable_hash(&UserId(7)), fast_stable_hash(&7u64));
+/// ```
+#[macro_export]
+macro_rules! impl_stable_hash_transparent {
+    ($T:ident$(<$lt:lifetime>)?) => {
+        $crate::impl_stable_hash!($T$(<$lt>)? (transparent));
+    };
+    ($T:ident$(<$lt:lifetime>)? ($Inner:ty)) => {
+        $crate::impl_stable_hash!($T$(<$lt>)? (transparent $Inner));
+    };
+}
+
+/// Implements StableHash for an enum. Each variant is given an explicit index,
+/// which must not change, so that variants may be added or re-ordered without
+/// changing the hash. For example:
+///
+/// ```
+/// # use stable_hash::impl_stable_hash_enum;
+/// enum Shape {
+///     Empty,
+///     Circle(u32),
+///     Rect { width: u32, height: u32 },
+/// }
+///
+/// impl_stable_hash_enum!(Shape {
+///     Empty = 0,
+///     Circle(radius) = 1,
+///     Rect { width, height } = 2,
+/// });
+/// ```
+///
+/// The payload of a variant is written at child(0). A variant with a single
+/// unnamed field writes it there directly, and otherwise each field is written at
+/// child(0).child(i). The index is then written at the parent address as an int.
+/// This matches the encoding of enums by SerdeStableHash.
+///
+/// Because the index is written even when the payload is default, eg: Circle(0)
+/// and Rect { width: 0, height: 0 } do not collide. The exception is index 0,
+/// which like the int 0 writes nothing. So, the variant with index 0 is the default
+/// value of the enum and should be the one representing "nothing", like Empty above.
+///
+/// A variant with a single unnamed field may instead be marked transparent, and
+/// given no index. It then hashes exactly like its value, with no index written,
+/// so that the variant may later be removed in favor of the bare inner type
+/// without changing the hash. If the type of its field is also given, as in
+/// Legacy(value: u32) below, the enum takes the TYPE_ID of the inner type for
+/// the same reason. Only one variant may be transparent. It can no
+/// longer be told apart from the other variants, so it collides with any of them
+/// that hashes the same as one of its values. eg: below, Legacy(0) collides with
+/// Empty, and Legacy(1) with Circle(0).
+///
+/// ```
+/// # use stable_hash::{fast_stable_hash, impl_stable_hash_enum};
+/// enum Shape {
+///     Empty,
+///     Circle(u32),
+///     Legacy(u32),
+/// }
+///
+/// impl_stable_hash_enum!(Shape {
+///     Empty = 0,
+///     Circle(radius) = 1,
+///     #[stable_hash(transparent)]
+///     Legacy(value: u32),
+/// });
+///
+/// assert_eq!(fast_stable_hash(&Shape::Legacy(7)), fast_stable_hash(&7u32));
+/// ```
+#[macro_export]
+macro_rules! impl_stable_hash_enum {
+    ($T:ident$(<$lt:lifetime>)? {
+        $(
+            $(#[stable_hash($transparent:ident)])?
+            $V:ident $(($($a:ident $(: $A:ty)?),*))? $({$($f:ident),*})? $(= $index:literal)?
+        ),* $(,)?
+    }) => {
+        impl$(<$lt>)? $crate::StableHash for $T$(<$lt>)? {
+            const TYPE_ID: Option<u64> = $crate::impl_stable_hash_enum!(
+                @type_id $([$($transparent)?] $(($($a $(: $A)?),*))?)*
+            );
+
+            fn stable_hash<H: $crate::StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+                // Indices must be unique, or two variants would collide
+                const _: () = {
+                    let indices: &[u64] = &[$($($index,)?)*];
+                    let mut i = 0;
+                    while i < indices.len() {
+                        let mut j = i + 1;
+                        while j < indices.len() {
+                            assert!(indices[i] != indices[j], "duplicate variant index");
+                            j += 1;
+                        }
+                        i += 1;
+                    }
+                };
+                // Two transparent variants would collide whenever their values do
+                const _: () = {
+                    let transparent: usize = 0 $(+ $crate::impl_stable_hash_enum!(@count $($transparent)?))*;
+                    assert!(transparent <= 1, "more than one transparent variant");
+                };
+
+                let index: u64 = match self {
+                    $(
+                        Self::$V $(($($a),*))? $({$($f),*})? => {
+                            $crate::impl_stable_hash_enum!(
+                                @variant [$($transparent)?] [$($index)?] field_address, state;
+                                $(($($a $(: $A)?),*))? $({$($f),*})?
+                            )
+                        }
+                    )*
+                };
+                $crate::StableHash::stable_hash(&index, field_address, state);
+            }
+        }
+    };
+    (@count transparent) => { 1 };
+    (@count) => { 0 };
+    (@type_id [transparent] ($a:ident: $A:ty) $($rest:tt)*) => {
+        <$A as $crate::StableHash>::TYPE_ID
+    };
+    (@type_id [transparent] ($a:ident) $($rest:tt)*) => { None };
+    (@type_id [] ($($payload:tt)*) $($rest:tt)*) => {
+        $crate::impl_stable_hash_enum!(@type_id $($rest)*)
+    };
+    (@type_id [] $($rest:tt)*) => {
+        $crate::impl_stable_hash_enum!(@type_id $($rest)*)
+    };
+    (@type_id) => { None };
+    (@variant [transparent] [] $field_address:ident, $state:ident; ($a:ident $(: $A:ty)?)) => {
+        return $crate::StableHash::stable_hash($a, $field_address, $state)
+    };
+    (@variant [] [$index:literal] $field_address:ident, $state:ident;
+        $(($($a:ident),*))? $({$($f:ident),*})?
+    ) => {{
+        $($crate::impl_stable_hash_enum!(@tuple $field_address, $state; $($a),*);)?
+        $($crate::impl_stable_hash_enum!(@fields $field_address, $state; $($f),*);)?
+        $index
+    }};
+    (@tuple $field_address:ident, $state:ident; $a:ident) => {
+        $crate::StableHash::stable_hash($a, $crate::FieldAddress::child(&$field_address, 0), $state);
+    };
+    (@tuple $field_address:ident, $state:ident; $($a:ident),*) => {
+        $crate::impl_stable_hash_enum!(@fields $field_address, $state; $($a),*);
+    };
+    (@fields $field_address:ident, $state:ident; $($f:ident),*) => {
+        #[allow(unused_variables)]
+        let payload = $crate::FieldAddress::child(&$field_address, 0);
+        #[allow(unused_mut, unused_assignments)]
+        let mut i = 0;
+        $(
+            $crate::StableHash::stable_hash($f, $crate::FieldAddress::child(&payload, i), $state);
+            i += 1;
+        )*
+    };
+}
+
+/// Implements StableHash for a field-less enum with explicit repr values, eg:
+/// `#[repr(u8)] enum Level { Low = 1, High = 2 }`. The variant is hashed as its
+/// declared discriminant, `*self as u8`, rather than its position in the source.
+/// So, variants may be re-ordered, or new ones added, without changing the hash
+/// as long as the declared values do not change. The enum must be Copy.
+///
+/// The repr defaults to u8, and another may be given as eg: `(Level: u16)`.
+/// Since ints of any size hash the same, widening the repr is backward compatible.
+/// A variant with the value 0 writes nothing, and so is the default of the enum.
+///
+/// ```
+/// # use stable_hash::{fast_stable_hash, impl_stable_hash_repr_enum};
+/// #[derive(Clone, Copy)]
+/// #[repr(u8)]
+/// enum Level {
+///     High = 2,
+///     Low = 1,
+/// }
+///
+/// impl_stable_hash_repr_enum!(Level);
+///
+/// assert_eq!(fast_stable_hash(&Level::High), fast_stable_hash(&2u8));
+/// ```
+#[macro_export]
+macro_rules! impl_stable_hash_repr_enum {
+    ($T:ident) => {
+        $crate::impl_stable_hash_repr_enum!($T: u8);
+    };
+    ($T:ident: $repr:ty) => {
+        impl $crate::StableHash for $T {
+            fn stable_hash<H: $crate::StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+                $crate::StableHash::stable_hash(&(*self as $repr), field_address, state);
+            }
+        }
+    };
+}
+
+/// Implements StableHash for a Result-like enum (eg: a custom type implementing
+/// std::ops::Try), where each variant has at most one unnamed field. Each variant
+/// is given an explicit discriminant, which must not change. For example:
+///
+/// ```
+/// # use stable_hash::{fast_stable_hash, impl_stable_hash_try_enum};
+/// enum Outcome<T, E> {
+///     Success(T),
+///     Failure(E),
+///     Cancelled,
+/// }
+///
+/// impl_stable_hash_try_enum!(Outcome<T, E> {
+///     Success(value) = 0,
+///     Failure(err) = 1,
+///     Cancelled = 2,
+/// });
+///
+/// let success: Outcome<u32, ()> = Outcome::Success(5);
+/// assert_eq!(fast_stable_hash(&success), fast_stable_hash(&Ok::<u32, ()>(5)));
+/// ```
+///
+/// The payload is written at child(0), and then the discriminant is written at
+/// the parent address as a single byte. Like Result, the discriminant is always
+/// written, so no variant is the default, and eg: Success(0) is distinguishable
+/// from both Failure(0) and an absent field. With the discriminants 0 for the Ok
+/// variant and 1 for the Err variant, this matches the encoding of Result.
+///
+/// Every type parameter must be StableHash.
+#[macro_export]
+macro_rules! impl_stable_hash_try_enum {
+    ($T:ident$(<$($G:ident),*>)? {
+        $($V:ident $(($a:ident))? = $d:literal),* $(,)?
+    }) => {
+        impl$(<$($G: $crate::StableHash),*>)? $crate::StableHash for $T$(<$($G),*>)? {
+            fn stable_hash<H: $crate::StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+                // Discriminants must be unique, or two variants would collide
+                const _: () = {
+                    let discriminants: &[u8] = &[$($d),*];
+                    let mut i = 0;
+                    while i < discriminants.len() {
+                        let mut j = i + 1;
+                        while j < discriminants.len() {
+                            assert!(discriminants[i] != discriminants[j], "duplicate discriminant");
+                            j += 1;
+                        }
+                        i += 1;
+                    }
+                };
+
+                let discriminant: u8 = match self {
+                    $(
+                        Self::$V $(($a))? => {
+                            $($crate::StableHash::stable_hash($a, $crate::FieldAddress::child(&field_address, 0), state);)?
+                            $d
+                        }
+                    )*
+                };
+                $crate::StableHasher::write(state, field_address, &[discriminant]);
+            }
+        }
+    };
+}
+
+/// Implements StableHash for a collection which implements OrderedCollection, so
+/// that it hashes exactly like a Vec of its items. Generic parameters are given
+/// without bounds, eg: `impl_stable_hash_ordered!(Ring<T>)`, and the impl applies
+/// wherever the collection implements OrderedCollection.
+#[macro_export]
+macro_rules! impl_stable_hash_ordered {
+    ($T:ident$(<$($G:ident),*>)?) => {
+        impl$(<$($G),*>)? $crate::StableHash for $T$(<$($G),*>)?
+        where
+            Self: $crate::utils::OrderedCollection,
+        {
+            fn stable_hash<H: $crate::StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+                $crate::utils::OrderedCollection::stable_hash_ordered(self, field_address, state)
+            }
+        }
+    };
+}
+
+/// Implements StableHash for a collection which implements UnorderedCollection,
+/// so that it hashes exactly like a HashSet of its members. Generic parameters
+/// are given as for impl_stable_hash_ordered!. See UnorderedCollection for an
+/// example.
+#[macro_export]
+macro_rules! impl_stable_hash_unordered {
+    ($T:ident$(<$($G:ident),*>)?) => {
+        impl$(<$($G),*>)? $crate::StableHash for $T$(<$($G),*>)?
+        where
+            Self: $crate::utils::UnorderedCollection,
+        {
+            fn stable_hash<H: $crate::StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+                $crate::utils::UnorderedCollection::stable_hash_unordered(self, field_address, state)
+            }
+        }
+    };
 }
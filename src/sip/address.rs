@@ -0,0 +1,64 @@
+use crate::prelude::*;
+
+/// A field address which carries the key that every field is hashed with.
+///
+/// The key must reach every write, including those to the independent hashers
+/// created for members of unordered collections. Since StableHasher::new has no
+/// way to receive a key, it is passed down through the address instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SipAddress {
+    pub(crate) key: (u64, u64),
+    pub(crate) address: u128,
+}
+
+impl SipAddress {
+    /// The root address for a given key
+    pub fn root_keyed(key: [u8; 16]) -> Self {
+        Self {
+            key: (
+                u64::from_le_bytes(key[0..8].try_into().unwrap()),
+                u64::from_le_bytes(key[8..16].try_into().unwrap()),
+            ),
+            address: u128::root(),
+        }
+    }
+}
+
+/// The same address scheme as FastStableHasher, keeping the key along the path.
+impl FieldAddress for SipAddress {
+    /// The root for the fixed key of 16 zero bytes. The key is known, so this
+    /// gives no protection against collisions constructed by an attacker. It is
+    /// only reachable within the crate, since SipAddress is not public, and
+    /// sip_stable_hash_keyed starts from root_keyed instead.
+    fn root() -> Self {
+        Self::root_keyed([0; 16])
+    }
+    #[inline]
+    fn child(&self, number: u64) -> Self {
+        Self {
+            key: self.key,
+            address: self.address.child(number),
+        }
+    }
+    #[inline]
+    fn named_child(&self, name: &str) -> Self {
+        Self {
+            key: self.key,
+            address: self.address.named_child(name),
+        }
+    }
+    #[inline]
+    fn unordered(&self) -> (Self, Self) {
+        let (member, result) = self.address.unordered();
+        (
+            Self {
+                key: self.key,
+                address: member,
+            },
+            Self {
+                key: self.key,
+                address: result,
+            },
+        )
+    }
+}
@@ -0,0 +1,89 @@
+use super::address::SipAddress;
+use crate::fast::fld::FldMix;
+use crate::prelude::*;
+use siphasher::sip128::{Hasher128, SipHasher13};
+use std::convert::TryInto;
+use std::hash::Hasher;
+
+/// The same construction as FastStableHasher, except that each field is hashed
+/// with SipHash-1-3 keyed by the key carried in its address rather than with xxh3.
+/// Since the combination of fields is only as predictable as the hashes of the
+/// fields themselves, the output cannot be predicted without the key.
+/// See also sip_stable_hash_keyed
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SipStableHasher {
+    mixer: FldMix,
+    count: u64,
+}
+
+impl StableHasher for SipStableHasher {
+    type Out = u64;
+    type Addr = SipAddress;
+    type Bytes = [u8; 32];
+
+    fn new() -> Self {
+        Self {
+            mixer: FldMix::new(),
+            count: 0,
+        }
+    }
+
+    fn write(&mut self, field_address: Self::Addr, bytes: &[u8]) {
+        profile_method!(write);
+
+        self.write_chunks(field_address, [bytes]);
+    }
+
+    fn write_chunks<'a>(
+        &mut self,
+        field_address: Self::Addr,
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) {
+        profile_method!(write_chunks);
+
+        let (k0, k1) = field_address.key;
+        let mut hasher = SipHasher13::new_with_keys(k0, k1);
+        // The address has a fixed size, so prefixing it keeps the input injective
+        hasher.write(&(field_address.address as u64).to_le_bytes());
+        // SipHash is streaming, so this is the same as writing the concatenation
+        for chunk in chunks {
+            hasher.write(chunk);
+        }
+        let hash = hasher.finish128().as_u128();
+        self.mixer.mix(hash, (field_address.address >> 64) as u64);
+        self.count += 1;
+    }
+
+    fn mixin(&mut self, other: &Self) {
+        self.mixer.mixin(&other.mixer);
+        self.count = self.count.wrapping_add(other.count);
+    }
+
+    fn unmix(&mut self, other: &Self) {
+        self.mixer.unmix(&other.mixer);
+        self.count = self.count.wrapping_sub(other.count);
+    }
+
+    fn to_bytes(&self) -> Self::Bytes {
+        let mut bytes = [0; 32];
+        bytes[0..24].copy_from_slice(&self.mixer.to_bytes());
+        bytes[24..32].copy_from_slice(&self.count.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: Self::Bytes) -> Self {
+        Self {
+            mixer: FldMix::from_bytes(bytes[0..24].try_into().unwrap()),
+            count: u64::from_le_bytes(bytes[24..32].try_into().unwrap()),
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        profile_method!(finish);
+
+        // The mixer already depends on the key, so a fixed key suffices here
+        let mut hasher = SipHasher13::new();
+        hasher.write(&self.to_bytes());
+        hasher.finish()
+    }
+}
@@ -0,0 +1,30 @@
+mod address;
+mod hasher;
+
+// The hasher and its address are not public. Generic entry points (eg:
+// stable_hash_with_stats or check_for_child_errors) start from
+// FieldAddress::root, which cannot be given a key, so they would silently hash
+// with a fixed key. sip_stable_hash_keyed is the only entry point, and it is
+// always keyed.
+pub(crate) use address::SipAddress;
+pub(crate) use hasher::SipStableHasher;
+
+use crate::prelude::*;
+
+/// Like fast_stable_hash, but keyed so that an attacker who does not know the key
+/// cannot construct values which collide. Suitable for eg: keys of a hash table
+/// which are derived from untrusted input, where collisions would allow denial of
+/// service but a cryptographic hash is more than necessary.
+///
+/// This is the only way to hash with SipStableHasher, so that every hash is
+/// keyed. The result is stable for a given key. Values which hash the same with
+/// fast_stable_hash (eg: when a default field is added) also hash the same with
+/// this for any key. A value which writes nothing (eg: a default value) hashes
+/// the same for every key.
+pub fn sip_stable_hash_keyed<T: StableHash>(value: &T, key: [u8; 16]) -> u64 {
+    profile_fn!(sip_stable_hash_keyed);
+
+    let mut hasher = SipStableHasher::new();
+    root_stable_hash(value, SipAddress::root_keyed(key), &mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,180 @@
+use crate::prelude::*;
+use crate::utils::AsBytes;
+use std::borrow::Cow;
+
+/// Treat some &[u64] (eg: a Vec<u64> or [u64; N] numeric buffer) as the little
+/// endian bytes of each word in order, rather than as a sequence of numbers.
+/// Hashes exactly like AsBytes over those bytes, regardless of the endianness of
+/// the target. Like AsBytes, this is much faster than hashing each number, but
+/// does not support the backward compatible change to different int types.
+///
+/// On little endian targets the buffer is already in this form, so it is hashed
+/// without conversion or copying. On big endian targets each word is byte
+/// swapped into a copy first. With the simd feature, that swap is done 16 bytes
+/// at a time with a vector byte shuffle where the target supports one (SSSE3 on
+/// x86, detected at runtime, and NEON on aarch64).
+pub struct AsU64Bytes<'a>(pub &'a [u64]);
+
+impl StableHash for AsU64Bytes<'_> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        let bytes = canonicalize(native_bytes(self.0), cfg!(target_endian = "little"));
+        AsBytes(&bytes).stable_hash(field_address, state)
+    }
+}
+
+/// The bytes of the words as they are laid out in memory
+fn native_bytes(words: &[u64]) -> &[u8] {
+    // SAFETY: u64 has no padding and every byte of it is initialized. The
+    // alignment of u8 is 1, and the length covers exactly the words.
+    unsafe { std::slice::from_raw_parts(words.as_ptr().cast::<u8>(), std::mem::size_of_val(words)) }
+}
+
+/// Converts the in-memory bytes of words with the given endianness to the little
+/// endian bytes of each word. The endianness is a parameter rather than checked
+/// here so that both paths can be tested on any target.
+fn canonicalize(native: &[u8], little_endian: bool) -> Cow<'_, [u8]> {
+    if little_endian {
+        return Cow::Borrowed(native);
+    }
+    let mut bytes = native.to_vec();
+    swap_words(&mut bytes);
+    Cow::Owned(bytes)
+}
+
+#[cfg(not(feature = "simd"))]
+fn swap_words(bytes: &mut [u8]) {
+    swap_words_scalar(bytes);
+}
+
+#[cfg(feature = "simd")]
+fn swap_words(bytes: &mut [u8]) {
+    let swapped = simd::swap_blocks(bytes);
+    swap_words_scalar(&mut bytes[swapped..]);
+}
+
+fn swap_words_scalar(bytes: &mut [u8]) {
+    for word in bytes.chunks_exact_mut(8) {
+        word.reverse();
+    }
+}
+
+/// Reverses the bytes of each word in whole 16 byte blocks from the start of the
+/// buffer, returning how many bytes were swapped. The rest is left to the
+/// scalar path.
+#[cfg(feature = "simd")]
+mod simd {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub(super) fn swap_blocks(bytes: &mut [u8]) -> usize {
+        if is_x86_feature_detected!("ssse3") {
+            // SAFETY: SSSE3 was detected above
+            unsafe { swap_blocks_ssse3(bytes) }
+        } else {
+            0
+        }
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[target_feature(enable = "ssse3")]
+    unsafe fn swap_blocks_ssse3(bytes: &mut [u8]) -> usize {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::*;
+
+        // Byte i of the output is byte i ^ 7 of the input
+        let shuffle = _mm_set_epi8(8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7);
+        let swapped = bytes.len() / 16 * 16;
+        for block in bytes[..swapped].chunks_exact_mut(16) {
+            // The loads and stores are unaligned, and each covers exactly the block
+            let words = _mm_loadu_si128(block.as_ptr().cast());
+            _mm_storeu_si128(block.as_mut_ptr().cast(), _mm_shuffle_epi8(words, shuffle));
+        }
+        swapped
+    }
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    pub(super) fn swap_blocks(bytes: &mut [u8]) -> usize {
+        use std::arch::aarch64::*;
+
+        let swapped = bytes.len() / 16 * 16;
+        for block in bytes[..swapped].chunks_exact_mut(16) {
+            // SAFETY: NEON is enabled for the target, and the load and store
+            // each cover exactly the block
+            unsafe {
+                let words = vld1q_u8(block.as_ptr());
+                vst1q_u8(block.as_mut_ptr(), vrev64q_u8(words));
+            }
+        }
+        swapped
+    }
+
+    #[cfg(not(any(
+        target_arch = "x86",
+        target_arch = "x86_64",
+        all(target_arch = "aarch64", target_feature = "neon")
+    )))]
+    pub(super) fn swap_blocks(_bytes: &mut [u8]) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words() -> Vec<u64> {
+        (0..37u64)
+            .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+            .collect()
+    }
+
+    fn expected(words: &[u64]) -> Vec<u8> {
+        words.iter().flat_map(|w| w.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn little_endian_is_unchanged() {
+        let words = words();
+        let native: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+        let bytes = canonicalize(&native, true);
+        assert!(matches!(bytes, Cow::Borrowed(_)));
+        assert_eq!(&*bytes, &expected(&words)[..]);
+    }
+
+    #[test]
+    fn big_endian_is_swapped() {
+        let words = words();
+        let native: Vec<u8> = words.iter().flat_map(|w| w.to_be_bytes()).collect();
+        assert_eq!(&*canonicalize(&native, false), &expected(&words)[..]);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn simd_matches_scalar() {
+        let words = words();
+        let native: Vec<u8> = words.iter().flat_map(|w| w.to_be_bytes()).collect();
+        for len in 0..=words.len() {
+            let mut scalar = native[..len * 8].to_vec();
+            swap_words_scalar(&mut scalar);
+            let mut simd = native[..len * 8].to_vec();
+            swap_words(&mut simd);
+            assert_eq!(simd, scalar);
+        }
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        if is_x86_feature_detected!("ssse3") {
+            assert_eq!(
+                simd::swap_blocks(&mut native.clone()),
+                native.len() / 16 * 16
+            );
+        }
+    }
+
+    #[test]
+    fn native_matches_target() {
+        let words = words();
+        let bytes = canonicalize(native_bytes(&words), cfg!(target_endian = "little"));
+        assert_eq!(&*bytes, &expected(&words)[..]);
+    }
+}
@@ -0,0 +1,53 @@
+use super::MapHashState;
+use crate::prelude::*;
+use std::collections::BTreeMap;
+
+/// Maintains the fast hash of a BTreeMap as single entries are added and removed,
+/// without rehashing the map. The result of finish() is always the same as
+/// fast_stable_hash of a map with the same entries.
+///
+/// A BTreeMap hashes exactly like a HashMap, as an unordered collection, so
+/// this shares its construction with MapHashState. In particular, maintaining the
+/// hash never needs to visit the entries in order.
+pub struct BTreeMapHashState<K, V> {
+    inner: MapHashState<K, V>,
+}
+
+impl<K, V> Default for BTreeMapHashState<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> BTreeMapHashState<K, V> {
+    /// The state of an empty map
+    pub fn new() -> Self {
+        Self {
+            inner: MapHashState::new(),
+        }
+    }
+
+    /// Same as fast_stable_hash of the map
+    pub fn finish(&self) -> u128 {
+        self.inner.finish()
+    }
+}
+
+impl<K: StableHash, V: StableHash> BTreeMapHashState<K, V> {
+    /// The state of the given map
+    pub fn from_map(map: &BTreeMap<K, V>) -> Self {
+        Self {
+            inner: MapHashState::from_entries(map),
+        }
+    }
+
+    /// Adds an entry which was not in the map
+    pub fn add_entry(&mut self, key: &K, value: &V) {
+        self.inner.insert(key, value)
+    }
+
+    /// Removes an entry which was in the map with the given value
+    pub fn remove_entry(&mut self, key: &K, value: &V) {
+        self.inner.remove(key, value)
+    }
+}
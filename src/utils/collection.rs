@@ -0,0 +1,65 @@
+use crate::impls::unordered_unique_stable_hash;
+use crate::impls::vec::sequence_stable_hash;
+use crate::prelude::*;
+
+/// A collection whose items are hashed in order, exactly like a Vec of the same
+/// items. To opt a custom collection into this encoding, implement this trait
+/// and then use impl_stable_hash_ordered! to implement StableHash.
+///
+/// There is no blanket impl of StableHash for these traits, since it would
+/// conflict with the impls for eg: &T and Box<T>, which a downstream crate could
+/// implement either trait for. The macros write the impl instead.
+pub trait OrderedCollection {
+    /// The items of the collection, in order
+    fn items(&self) -> impl Iterator<Item = impl StableHash + '_> + '_;
+
+    /// Hashes the collection. See impl_stable_hash_ordered!
+    fn stable_hash_ordered<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash_ordered);
+
+        sequence_stable_hash(self.items(), field_address, state)
+    }
+}
+
+/// A collection whose members are hashed independently of their order, exactly
+/// like a HashSet of the same members. To opt a custom collection into this
+/// encoding, implement this trait and then use impl_stable_hash_unordered! to
+/// implement StableHash.
+///
+/// Repeated members are each hashed, so that the collection hashes like a
+/// multiset (eg: a BinaryHeap). A set which contains each member once therefore
+/// hashes exactly like a HashSet.
+///
+/// ```
+/// use stable_hash::utils::UnorderedCollection;
+/// use stable_hash::{fast_stable_hash, impl_stable_hash_unordered, StableHash};
+/// use std::collections::HashSet;
+///
+/// /// A small set, kept in a Vec in insertion order
+/// struct VecSet<T>(Vec<T>);
+///
+/// impl<T: StableHash> UnorderedCollection for VecSet<T> {
+///     fn members(&self) -> impl Iterator<Item = impl StableHash + '_> + '_ {
+///         self.0.iter()
+///     }
+/// }
+///
+/// impl_stable_hash_unordered!(VecSet<T>);
+///
+/// let a = VecSet(vec![1u32, 2, 3]);
+/// let b = VecSet(vec![3u32, 1, 2]);
+/// let set: HashSet<u32> = [2, 3, 1].into_iter().collect();
+/// assert_eq!(fast_stable_hash(&a), fast_stable_hash(&b));
+/// assert_eq!(fast_stable_hash(&a), fast_stable_hash(&set));
+/// ```
+pub trait UnorderedCollection {
+    /// The members of the collection, in any order
+    fn members(&self) -> impl Iterator<Item = impl StableHash + '_> + '_;
+
+    /// Hashes the collection. See impl_stable_hash_unordered!
+    fn stable_hash_unordered<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash_unordered);
+
+        unordered_unique_stable_hash(self.members(), field_address, state)
+    }
+}
@@ -0,0 +1,91 @@
+use crate::fast::FastStableHasher;
+use crate::prelude::*;
+use std::ops::{Deref, DerefMut};
+
+/// Accumulates a single hash from many threads at once.
+///
+/// A StableHasher requires &mut to write, so it cannot be shared between threads
+/// directly. Instead, take a LocalStableHasher for each thread via local() and
+/// move it into the thread, which writes to it without any synchronization. When
+/// the thread is done, return the local hasher (eg: as the result of a scoped
+/// thread) and pass it to combine, which adds its state with mixin. Nothing is
+/// locked or shared while the threads run.
+///
+/// Because mixin is order independent, the result is the same as if every field
+/// had been written to one FastStableHasher by a single thread, regardless of
+/// which thread wrote which field or in what order the threads finished. As with
+/// any single hasher, each field address should be written by only one thread.
+///
+/// finish panics if any local hasher was not combined, rather than silently
+/// leaving out the fields written to it.
+pub struct ConcurrentStableHasher {
+    combined: FastStableHasher,
+    outstanding: usize,
+}
+
+impl Default for ConcurrentStableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConcurrentStableHasher {
+    pub fn new() -> Self {
+        Self {
+            combined: FastStableHasher::new(),
+            outstanding: 0,
+        }
+    }
+
+    /// A hasher for a single thread, which must later be passed to combine.
+    pub fn local(&mut self) -> LocalStableHasher {
+        self.outstanding += 1;
+        LocalStableHasher {
+            hasher: FastStableHasher::new(),
+        }
+    }
+
+    /// Adds every field written to a local hasher.
+    pub fn combine(&mut self, local: LocalStableHasher) {
+        profile_method!(combine);
+
+        assert!(
+            self.outstanding > 0,
+            "Combined a local hasher which was not taken from this ConcurrentStableHasher"
+        );
+        self.outstanding -= 1;
+        self.combined.mixin(&local.hasher);
+    }
+
+    /// The hash of every field written by the local hashers.
+    ///
+    /// Panics if any local hasher has not been combined.
+    pub fn finish(self) -> u128 {
+        profile_method!(finish);
+
+        assert_eq!(
+            self.outstanding, 0,
+            "Every local hasher must be combined before finishing"
+        );
+        self.combined.finish()
+    }
+}
+
+/// See ConcurrentStableHasher::local
+#[must_use = "The fields written to a local hasher are only included once it is combined"]
+pub struct LocalStableHasher {
+    hasher: FastStableHasher,
+}
+
+impl Deref for LocalStableHasher {
+    type Target = FastStableHasher;
+    fn deref(&self) -> &FastStableHasher {
+        &self.hasher
+    }
+}
+
+impl DerefMut for LocalStableHasher {
+    fn deref_mut(&mut self) -> &mut FastStableHasher {
+        &mut self.hasher
+    }
+}
@@ -0,0 +1,40 @@
+use crate::fast::FastStableHasher;
+use crate::prelude::*;
+
+/// An object safe form of StableHash, so that values of different types can be
+/// hashed through a Box<dyn DynStableHash>. eg: the values of a HashMap of
+/// heterogeneous config values. This is implemented for every StableHash.
+///
+/// StableHash can not be object safe, since it is generic over the hasher. So,
+/// this is fixed to a single backend: the value is hashed into its own
+/// FastStableHasher, and the serialized state of that hasher is what a
+/// Box<dyn DynStableHash> writes, with any backend. This has two consequences:
+///  * The boxed value does not hash the same as the value itself.
+///  * When hashed with CryptoStableHasher, the boxed value only has the collision
+///    resistance of the fast backend.
+pub trait DynStableHash {
+    /// The state of a FastStableHasher after hashing the value at the root
+    fn fast_stable_hasher(&self) -> FastStableHasher;
+}
+
+impl<T: StableHash> DynStableHash for T {
+    fn fast_stable_hasher(&self) -> FastStableHasher {
+        profile_method!(fast_stable_hasher);
+
+        let mut hasher = FastStableHasher::new();
+        self.stable_hash(FieldAddress::root(), &mut hasher);
+        hasher
+    }
+}
+
+impl StableHash for Box<dyn DynStableHash + '_> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        let hasher = (**self).fast_stable_hasher();
+        // The empty value is the default, like any other
+        if hasher != FastStableHasher::new() {
+            state.write(field_address, &hasher.to_bytes());
+        }
+    }
+}
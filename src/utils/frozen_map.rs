@@ -0,0 +1,96 @@
+use crate::fast::{FastAddress, FastStableHasher};
+use crate::impls::{unordered_member_hasher, MapEntry};
+use crate::prelude::*;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::ops::Deref;
+
+/// A HashMap which can no longer be modified, along with what each of its
+/// entries contributes to the fast hash, computed once at construction.
+/// Hashing the map with FastStableHasher, whether at the root or nested in
+/// another value, reuses those contributions rather than rehashing every entry.
+/// The hash is always the same as that of the underlying map, as long as the
+/// hash of no entry changes (see below).
+///
+/// This works because each entry of a map is hashed into its own hasher at the
+/// root address, no matter where the map is, so what it writes only depends on
+/// the entry. Other backends do not share this property for every address, so
+/// hashing with them (eg: with crypto_stable_hash) rehashes the map.
+///
+/// The map is still available for lookups through Deref. The hash of each entry
+/// must not change while it is frozen. A value with interior mutability (eg:
+/// Cell or Mutex) can still be changed through Deref, but its cached
+/// contribution is not, so the fast hash would silently no longer match the map.
+pub struct FrozenMap<K, V, S = std::collections::hash_map::RandomState> {
+    map: HashMap<K, V, S>,
+    /// The state of the member hasher of each entry with FastStableHasher
+    states: Vec<[u8; 32]>,
+}
+
+impl<K: StableHash, V: StableHash, S> FrozenMap<K, V, S> {
+    pub fn new(map: HashMap<K, V, S>) -> Self {
+        profile_method!(new);
+
+        let (member_address, _) = FastAddress::root().unordered();
+        let states = map
+            .iter()
+            .map(|(key, value)| {
+                let entry = MapEntry(key, value);
+                unordered_member_hasher::<FastStableHasher>(&entry, member_address).to_bytes()
+            })
+            .collect();
+        Self { map, states }
+    }
+
+    /// The same as fast_stable_hash of the map, without rehashing its entries.
+    /// This is also what fast_stable_hash of the FrozenMap does.
+    pub fn fast_stable_hash(&self) -> u128 {
+        profile_method!(fast_stable_hash);
+
+        let mut state = FastStableHasher::new();
+        self.stable_hash_fast(FastAddress::root(), &mut state);
+        state.finish()
+    }
+
+    /// The same as stable_hash of the map with FastStableHasher, without
+    /// rehashing its entries. The StableHash impl calls this when hashing with
+    /// FastStableHasher.
+    pub fn stable_hash_fast(&self, field_address: FastAddress, state: &mut FastStableHasher) {
+        profile_method!(stable_hash_fast);
+
+        for bytes in &self.states {
+            let (_, result_address) = field_address.unordered();
+            state.write_member(result_address, &FastStableHasher::from_bytes(*bytes));
+        }
+    }
+}
+
+impl<K, V, S> FrozenMap<K, V, S> {
+    pub fn into_inner(self) -> HashMap<K, V, S> {
+        self.map
+    }
+}
+
+impl<K, V, S> Deref for FrozenMap<K, V, S> {
+    type Target = HashMap<K, V, S>;
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl<K: StableHash, V: StableHash, S> StableHash for FrozenMap<K, V, S> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        // H is not required to be 'static, so it is compared with typeid
+        if typeid::of::<H>() == TypeId::of::<FastStableHasher>() {
+            // SAFETY: H is FastStableHasher, so H::Addr is FastAddress. Both are
+            // Copy, so reading the address leaves nothing to drop twice.
+            let state = unsafe { &mut *(state as *mut H).cast::<FastStableHasher>() };
+            let field_address = unsafe { std::mem::transmute_copy(&field_address) };
+            self.stable_hash_fast(field_address, state);
+        } else {
+            self.map.stable_hash(field_address, state)
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::prelude::*;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+/// Compares values by their fast stable hash rather than by structural equality,
+/// eg: so that large values which are expensive to compare can be kept in a
+/// HashSet<HashEq<T>>. The hash is computed once, when the wrapper is created,
+/// and the value can not be mutated afterward.
+///
+/// Values which are equal always compare equal. But, two values which are not
+/// equal compare equal if their hashes collide. With 128 bits this is very
+/// unlikely, but the fast backend is not collision resistant, so do not use this
+/// where the values may be chosen by an adversary. Note also that values which
+/// differ only in ways that StableHash ignores (eg: the order of a HashMap, or
+/// default fields) compare equal, as they hash the same.
+///
+/// Hashes exactly like the wrapped value.
+#[derive(Clone, Debug)]
+pub struct HashEq<T> {
+    value: T,
+    hash: u128,
+}
+
+impl<T: StableHash> HashEq<T> {
+    pub fn new(value: T) -> Self {
+        profile_method!(new);
+
+        let hash = crate::fast_stable_hash(&value);
+        Self { value, hash }
+    }
+}
+
+impl<T> HashEq<T> {
+    /// The fast stable hash of the value
+    pub fn fast_hash(&self) -> u128 {
+        self.hash
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for HashEq<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> PartialEq for HashEq<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash
+    }
+}
+
+impl<T> Eq for HashEq<T> {}
+
+/// Consistent with PartialEq, since equal wrappers have equal stable hashes
+impl<T> Hash for HashEq<T> {
+    fn hash<S: Hasher>(&self, state: &mut S) {
+        self.hash.hash(state)
+    }
+}
+
+impl<T: StableHash> StableHash for HashEq<T> {
+    const TYPE_ID: Option<u64> = T::TYPE_ID;
+    const IS_UNIT: bool = T::IS_UNIT;
+
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        self.value.stable_hash(field_address, state)
+    }
+}
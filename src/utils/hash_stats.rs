@@ -0,0 +1,104 @@
+use crate::prelude::*;
+
+/// Counts of the work done when hashing a value. See stable_hash_with_stats.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HashStats {
+    /// The number of fields written, ie: calls to write. A field whose payload
+    /// is split into chunks counts once.
+    pub elements_hashed: u64,
+    /// The total length of the payloads of every field written
+    pub bytes_written: u64,
+    /// The number of independent hashers created for the members of unordered
+    /// collections (eg: one per entry of a HashMap)
+    pub sub_hashers_allocated: u64,
+}
+
+/// Hashes the value with H, returning the same output as hashing it directly,
+/// along with counts of the work that was done. This shows how expensive a
+/// structure is to hash, eg: when tuning the use of large unordered collections.
+///
+/// Fields written by the members of unordered collections are counted as well,
+/// in addition to the write of each member's serialized state to the parent.
+pub fn stable_hash_with_stats<T: StableHash, H: StableHasher>(value: &T) -> (H::Out, HashStats) {
+    profile_fn!(stable_hash_with_stats);
+
+    let mut hasher = StatsHasher::<H>::new();
+    root_stable_hash(value, H::Addr::root(), &mut hasher);
+    (hasher.finish(), hasher.stats)
+}
+
+/// Delegates to H, counting each write. The address and serialized state are
+/// those of H, so the output is unchanged.
+///
+/// The hashers for members of unordered collections are created with new(), so
+/// each counts its own writes. They are added to the parent when the member is
+/// written to it, which is also where the member hasher is counted.
+struct StatsHasher<H> {
+    inner: H,
+    stats: HashStats,
+}
+
+impl<H: StableHasher> StableHasher for StatsHasher<H> {
+    type Out = H::Out;
+    type Addr = H::Addr;
+    type Bytes = H::Bytes;
+
+    fn new() -> Self {
+        Self {
+            inner: H::new(),
+            stats: HashStats::default(),
+        }
+    }
+    fn write(&mut self, field_address: Self::Addr, bytes: &[u8]) {
+        self.stats.elements_hashed += 1;
+        self.stats.bytes_written += bytes.len() as u64;
+        self.inner.write(field_address, bytes);
+    }
+    fn write_chunks<'a>(
+        &mut self,
+        field_address: Self::Addr,
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) {
+        self.stats.elements_hashed += 1;
+        let bytes_written = &mut self.stats.bytes_written;
+        let chunks = chunks.into_iter().inspect(|chunk| {
+            *bytes_written += chunk.len() as u64;
+        });
+        self.inner.write_chunks(field_address, chunks);
+    }
+    fn write_member(&mut self, result_address: Self::Addr, member: &Self) -> usize {
+        let len = self.inner.write_member(result_address, &member.inner);
+        self.stats.add(&member.stats);
+        self.stats.sub_hashers_allocated += 1;
+        self.stats.elements_hashed += 1;
+        self.stats.bytes_written += len as u64;
+        len
+    }
+    fn mixin(&mut self, other: &Self) {
+        self.stats.add(&other.stats);
+        self.inner.mixin(&other.inner);
+    }
+    fn unmix(&mut self, other: &Self) {
+        self.inner.unmix(&other.inner);
+    }
+    fn finish(&self) -> Self::Out {
+        self.inner.finish()
+    }
+    fn to_bytes(&self) -> Self::Bytes {
+        self.inner.to_bytes()
+    }
+    fn from_bytes(bytes: Self::Bytes) -> Self {
+        Self {
+            inner: H::from_bytes(bytes),
+            stats: HashStats::default(),
+        }
+    }
+}
+
+impl HashStats {
+    fn add(&mut self, other: &HashStats) {
+        self.elements_hashed += other.elements_hashed;
+        self.bytes_written += other.bytes_written;
+        self.sub_hashers_allocated += other.sub_hashers_allocated;
+    }
+}
@@ -0,0 +1,34 @@
+use crate::impls::unordered_unique_stable_hash;
+use crate::prelude::*;
+use std::collections::HashMap;
+
+// The hash of a map is the unordered collection of its entries, each hashed as a
+// (key, value) tuple. A key commitment and a value commitment are the unordered
+// collections of the keys and of the values alone. So, the hash of a map cannot
+// be computed from its two commitments: it also commits to which value is under
+// which key. Two maps which differ only in which keys their values are under
+// have equal commitments, but different hashes.
+
+/// A commitment to the keys of the map, but not the values, as a hasher so that
+/// commitments to disjoint maps can be combined with mixin. finish() is the same
+/// as the hash of a HashSet of the keys, so keys can be compared with a peer
+/// without sending the values.
+pub fn key_commitment<H: StableHasher, K: StableHash, V, S>(map: &HashMap<K, V, S>) -> H {
+    profile_fn!(key_commitment);
+
+    let mut commitment = H::new();
+    unordered_unique_stable_hash(map.keys(), H::Addr::root(), &mut commitment);
+    commitment
+}
+
+/// A commitment to the values of the map, but not the keys, as a hasher so that
+/// commitments to disjoint maps can be combined with mixin. Values which repeat
+/// each contribute, so finish() is the same as the hash of a BinaryHeap of the
+/// values.
+pub fn value_commitment<H: StableHasher, K, V: StableHash, S>(map: &HashMap<K, V, S>) -> H {
+    profile_fn!(value_commitment);
+
+    let mut commitment = H::new();
+    unordered_unique_stable_hash(map.values(), H::Addr::root(), &mut commitment);
+    commitment
+}
@@ -0,0 +1,176 @@
+use crate::fast::FastStableHasher;
+use crate::impls::root_map_entry;
+use crate::prelude::*;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+
+/// Maintains the fast hash of a HashMap (or BTreeMap) as entries are inserted,
+/// updated, and removed, without rehashing the whole map each time.
+///
+/// Each entry of a map contributes independently to the hash. So, an entry can be
+/// removed by unmixing its contribution and added by mixing it in. The result of
+/// finish() is always the same as fast_stable_hash of a map with the same entries.
+///
+/// The caller is responsible for supplying the values actually in the map. eg:
+/// removing an entry which was never inserted gives a meaningless result.
+///
+/// Updates do not allocate, so they are suitable for a hot loop. The hasher for
+/// the contribution of each entry, and its serialized state, live on the stack
+/// and are discarded after being mixed in or unmixed. There is no scratch hasher
+/// to reuse, since creating a FastStableHasher only initializes 32 bytes. This
+/// assumes that hashing the key and value does not allocate either, which holds
+/// for eg: ints, &str, and String, but not for values hashed via a temporary
+/// (eg: ViaDisplay).
+pub struct MapHashState<K, V> {
+    hasher: FastStableHasher,
+    _marker: PhantomData<fn(&K, &V)>,
+}
+
+impl<K, V> Clone for MapHashState<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            hasher: self.hasher.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V> Default for MapHashState<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> MapHashState<K, V> {
+    /// The state of an empty map
+    pub fn new() -> Self {
+        Self {
+            hasher: FastStableHasher::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Same as fast_stable_hash of the map
+    pub fn finish(&self) -> u128 {
+        self.hasher.finish()
+    }
+}
+
+impl<K: StableHash, V: StableHash> MapHashState<K, V> {
+    /// The state of a map with the given entries
+    pub fn from_entries<'a>(entries: impl IntoIterator<Item = (&'a K, &'a V)>) -> Self
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let mut state = Self::new();
+        for (key, value) in entries {
+            state.insert(key, value);
+        }
+        state
+    }
+
+    /// Adds an entry which was not in the map
+    pub fn insert(&mut self, key: &K, value: &V) {
+        profile_method!(insert);
+
+        self.hasher.mixin(&root_map_entry(key, value));
+    }
+
+    /// Removes an entry which was in the map with the given value
+    pub fn remove(&mut self, key: &K, value: &V) {
+        profile_method!(remove);
+
+        self.hasher.unmix(&root_map_entry(key, value));
+    }
+
+    /// Changes the value of an entry from old_value to new_value
+    pub fn update_entry(&mut self, key: &K, old_value: &V, new_value: &V) {
+        profile_method!(update_entry);
+
+        self.remove(key, old_value);
+        self.insert(key, new_value);
+    }
+}
+
+/// The state of the entries in superset which are not in subset, ie: the
+/// superset with every entry of the subset removed. When a remote peer sends the
+/// state of its map, this is the state of the entries it has that we do not.
+///
+/// The entries of the subset must all be in the superset with the same values.
+/// Otherwise the result is meaningless, rather than the hash of a symmetric
+/// difference. Only the number of fields written to each state can be checked.
+/// Each entry of a map state writes exactly one field, the state of its own
+/// hasher, so in debug builds this panics if the subset has more entries than
+/// the superset.
+pub fn difference_hash<K, V>(
+    superset: &MapHashState<K, V>,
+    subset: &MapHashState<K, V>,
+) -> MapHashState<K, V> {
+    profile_fn!(difference_hash);
+
+    debug_assert!(
+        subset.hasher.count() <= superset.hasher.count(),
+        "subset has more entries than superset"
+    );
+    let mut difference = superset.clone();
+    difference.hasher.unmix(&subset.hasher);
+    difference
+}
+
+/// The state of the symmetric difference of the entries of a and b, ie: the
+/// entries which are in exactly one of the maps. Entries which are in both maps
+/// with equal values are not hashed at all, so this is cheap when few entries
+/// changed between two snapshots of a map.
+///
+/// A key whose value differs between the maps contributes both of its entries,
+/// (key, old value) and (key, new value). So, the result is the same as a map of
+/// the differing entries only when no key changed its value. Otherwise there
+/// is no such map, and the result is the same as hashing a set of the differing
+/// entries as (key, value) tuples.
+pub fn symmetric_difference_hash<K, V, S>(
+    a: &HashMap<K, V, S>,
+    b: &HashMap<K, V, S>,
+) -> MapHashState<K, V>
+where
+    K: StableHash + Eq + Hash,
+    V: StableHash + PartialEq,
+    S: BuildHasher,
+{
+    profile_fn!(symmetric_difference_hash);
+
+    let mut state = MapHashState::new();
+    for (from, to) in [(a, b), (b, a)] {
+        for (key, value) in from {
+            if to.get(key) != Some(value) {
+                state.insert(key, value);
+            }
+        }
+    }
+    state
+}
+
+/// The state of the map with the entries of both a and b, which must not share
+/// any keys. Since each entry contributes independently to the hash, this mixes
+/// the states of the two maps together, and is the same as fast_stable_hash of
+/// the merged map. In debug builds this panics if a key is in both maps.
+pub fn merge_disjoint_hash<K, V, S>(
+    a: &HashMap<K, V, S>,
+    b: &HashMap<K, V, S>,
+) -> MapHashState<K, V>
+where
+    K: StableHash + Eq + Hash,
+    V: StableHash,
+    S: BuildHasher,
+{
+    profile_fn!(merge_disjoint_hash);
+
+    debug_assert!(
+        a.keys().all(|key| !b.contains_key(key)),
+        "maps are not disjoint"
+    );
+    let mut merged = MapHashState::from_entries(a);
+    merged.hasher.mixin(&MapHashState::from_entries(b).hasher);
+    merged
+}
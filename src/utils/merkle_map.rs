@@ -0,0 +1,110 @@
+use crate::prelude::*;
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+
+/// An inclusion proof for a single entry of a map. See merkle_map_proof
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleProof {
+    /// The sibling of each node on the path from the leaf to the root, and
+    /// whether that sibling is on the left.
+    pub siblings: Vec<(bool, [u8; 32])>,
+}
+
+/// The root of a Merkle tree over the entries of the map, in key order.
+///
+/// Each leaf is the crypto hash of an entry as a (key, value) tuple. Leaves and
+/// interior nodes are hashed with blake3 with a distinct prefix byte, 0 for a
+/// leaf and 1 for the concatenation of two child nodes, so that one cannot be
+/// mistaken for the other. Nodes are paired left to right at each level, and an
+/// unpaired node at the end of a level moves up a level unchanged. The root of
+/// an empty map is 32 zero bytes.
+///
+/// Unlike crypto_stable_hash of the map, this depends on the order of the keys,
+/// and so is only for a BTreeMap. In exchange, the inclusion of any one entry can
+/// be shown with a proof of a size logarithmic in the size of the map.
+pub fn merkle_map_root<K: StableHash, V: StableHash>(map: &BTreeMap<K, V>) -> [u8; 32] {
+    profile_fn!(merkle_map_root);
+
+    let mut level: Vec<_> = map.iter().map(|(key, value)| leaf(key, value)).collect();
+    if level.is_empty() {
+        return [0; 32];
+    }
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// A proof that the entry with the key is in the map, which can be checked
+/// against merkle_map_root with verify_map_proof. None if the key is not in the
+/// map.
+pub fn merkle_map_proof<K, V, Q>(map: &BTreeMap<K, V>, key: &Q) -> Option<MerkleProof>
+where
+    K: StableHash + Ord + Borrow<Q>,
+    V: StableHash,
+    Q: Ord + ?Sized,
+{
+    profile_fn!(merkle_map_proof);
+
+    let mut index = map.keys().position(|k| k.borrow() == key)?;
+    let mut level: Vec<_> = map.iter().map(|(key, value)| leaf(key, value)).collect();
+    let mut siblings = Vec::new();
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+        if sibling < level.len() {
+            siblings.push((sibling < index, level[sibling]));
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    Some(MerkleProof { siblings })
+}
+
+/// Whether the proof shows that the map with the root has the entry.
+pub fn verify_map_proof<K: StableHash, V: StableHash>(
+    root: &[u8; 32],
+    key: &K,
+    value: &V,
+    proof: &MerkleProof,
+) -> bool {
+    profile_fn!(verify_map_proof);
+
+    let computed = proof
+        .siblings
+        .iter()
+        .fold(leaf(key, value), |hash, (is_left, sibling)| {
+            if *is_left {
+                node(sibling, &hash)
+            } else {
+                node(&hash, sibling)
+            }
+        });
+    &computed == root
+}
+
+fn leaf<K: StableHash, V: StableHash>(key: &K, value: &V) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[0]);
+    hasher.update(&crate::crypto_stable_hash(&(key, value)));
+    *hasher.finalize().as_bytes()
+}
+
+/// Pairs the nodes of a level, moving an unpaired node at the end up unchanged
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node(left, right),
+            [single] => *single,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[1]);
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
@@ -0,0 +1,364 @@
+use crate::prelude::*;
+use crate::verification::*;
+
+mod as_u64_bytes;
+mod btree_map_hash_state;
+mod collection;
+mod concurrent;
+mod dyn_stable_hash;
+mod frozen_map;
+mod hash_eq;
+mod hash_stats;
+mod map_commitment;
+mod map_hash_state;
+mod merkle_map;
+mod packed_bools;
+mod range_commitment;
+mod set_commitment;
+mod sorted_by_hash;
+
+pub use as_u64_bytes::AsU64Bytes;
+pub use btree_map_hash_state::BTreeMapHashState;
+pub use collection::{OrderedCollection, UnorderedCollection};
+pub use concurrent::{ConcurrentStableHasher, LocalStableHasher};
+pub use dyn_stable_hash::DynStableHash;
+pub use frozen_map::FrozenMap;
+pub use hash_eq::HashEq;
+pub use hash_stats::{stable_hash_with_stats, HashStats};
+pub use map_commitment::{key_commitment, value_commitment};
+pub use map_hash_state::{
+    difference_hash, merge_disjoint_hash, symmetric_difference_hash, MapHashState,
+};
+pub use merkle_map::{merkle_map_proof, merkle_map_root, verify_map_proof, MerkleProof};
+#[cfg(feature = "bitvec")]
+pub(crate) use packed_bools::packed_bits_stable_hash;
+pub use packed_bools::PackedBools;
+pub use range_commitment::range_commitment;
+pub use set_commitment::{commit_set, SetCommitment};
+pub use sorted_by_hash::sorted_by_hash_digest;
+
+pub use crate::impls::binary_heap::SortedHeap;
+pub use crate::impls::floats::CanonicalFloat;
+pub use crate::impls::hash_map::stable_hash_filtered;
+pub use crate::impls::linked_list::concat_hash;
+pub use crate::impls::option::CompactOption;
+#[cfg(feature = "serde")]
+pub use crate::impls::serde::{Error as SerdeStableHashError, SerdeStableHash};
+#[cfg(feature = "unicode-normalization")]
+pub use crate::impls::unicode::NormalizedStr;
+#[cfg(feature = "trace")]
+pub use crate::verification::{canonical_encoding, map_golden_json};
+#[cfg(feature = "test-utils")]
+pub use crate::verification::{collision_stats, CollisionReport};
+pub use crate::verification::{diff_fields, schema_and_data_hash, FieldPath, PathItem};
+
+/// Treat some &[u8] as a sequence of bytes, rather than a sequence of numbers.
+/// Using this can result in a significant performance gain but does not support
+/// the backward compatible change to different int types as numbers do by default
+///
+/// Exactly the slice.len() bytes of the slice are read, and nothing around them.
+/// So, when hashing part of a larger buffer, the rest of the buffer (eg: spare
+/// capacity which was never written) does not affect the hash. Since a &[u8]
+/// must only refer to initialized bytes, a partially initialized buffer must be
+/// sliced to its initialized part before hashing. Creating a slice over bytes
+/// which were never written (eg: with from_raw_parts over MaybeUninit) is
+/// undefined behavior, and cannot be caught here.
+pub struct AsBytes<'a>(pub &'a [u8]);
+
+impl StableHash for AsBytes<'_> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        if !self.0.is_empty() {
+            state.write(field_address, self.0)
+        }
+    }
+}
+
+/// A sequence of bytes which is split into chunks, eg: a large file read in
+/// pieces. Hashes exactly like AsBytes over the concatenated chunks, no matter
+/// where the chunks are split, without the whole payload being held in memory.
+///
+/// The iterator is cloned each time the value is hashed, which is cheap for eg:
+/// slice::Chunks. To hash from an iterator which can only be consumed once, use
+/// byte_chunks_stable_hash from the StableHash impl of the containing type.
+///
+/// Note that AsBytes never copies its payload either, so there is no need to
+/// chunk a slice that is already contiguous in memory.
+pub struct AsByteChunks<I>(pub I);
+
+impl<'a, I> StableHash for AsByteChunks<I>
+where
+    I: IntoIterator<Item = &'a [u8]> + Clone,
+{
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        byte_chunks_stable_hash(self.0.clone(), field_address, state)
+    }
+}
+
+/// Hashes the chunks exactly like AsBytes over the concatenated chunks. The
+/// chunks are consumed in a single pass. See also AsByteChunks
+pub fn byte_chunks_stable_hash<'a, H: StableHasher>(
+    chunks: impl IntoIterator<Item = &'a [u8]>,
+    field_address: H::Addr,
+    state: &mut H,
+) {
+    profile_fn!(byte_chunks_stable_hash);
+
+    // Same as AsBytes, an empty payload is the default. Leading empty chunks do
+    // not change the payload, so they are skipped to find out whether it is empty.
+    let mut chunks = chunks
+        .into_iter()
+        .skip_while(|chunk| chunk.is_empty())
+        .peekable();
+    if chunks.peek().is_some() {
+        state.write_chunks(field_address, chunks)
+    }
+}
+
+/// The bytes of a public key, eg: a 32 byte ed25519 key or a 33 byte compressed
+/// secp256k1 key.
+///
+/// Keys and signatures are opaque bytes rather than sequences of numbers, so
+/// they should be hashed with a single write via AsBytes rather than one write
+/// per byte. This wrapper, and SignatureBytes, make that the obvious choice for
+/// fixed size arrays. The hash is the same as AsBytes over the same bytes, so
+/// changing a field between [u8; N] wrapped in this type and Vec<u8> wrapped
+/// in AsBytes is backward compatible.
+pub struct PublicKeyBytes<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> StableHash for PublicKeyBytes<N> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        AsBytes(&self.0).stable_hash(field_address, state)
+    }
+}
+
+/// The bytes of a signature, eg: a 64 byte ed25519 or secp256k1 signature.
+/// See also PublicKeyBytes
+pub struct SignatureBytes<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> StableHash for SignatureBytes<N> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        AsBytes(&self.0).stable_hash(field_address, state)
+    }
+}
+
+/// A stable identity for a function, eg: a handler in a plugin system.
+///
+/// Function pointers are intentionally not StableHash. Their value is an address,
+/// which differs between builds, platforms, and even runs of the same program
+/// (due to address space layout randomization). Instead, give each handler a
+/// name which does not change, and hash that. Hashes the same as the name.
+///
+/// ```
+/// use stable_hash::fast_stable_hash;
+/// use stable_hash::utils::DispatchKey;
+///
+/// struct Handler {
+///     key: DispatchKey,
+///     run: fn(&str) -> usize,
+/// }
+///
+/// const COUNT_BYTES: Handler = Handler {
+///     key: DispatchKey("count_bytes"),
+///     run: str::len,
+/// };
+///
+/// // Identify the handler by its key, not by the address of run
+/// let id = fast_stable_hash(&COUNT_BYTES.key);
+/// assert_eq!(id, fast_stable_hash(&"count_bytes"));
+/// assert_eq!((COUNT_BYTES.run)("abc"), 3);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DispatchKey(pub &'static str);
+
+impl StableHash for DispatchKey {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        self.0.stable_hash(field_address, state)
+    }
+}
+
+/// The exit code of a process, hashed portably as the integer code.
+///
+/// std::process::ExitStatus is intentionally not StableHash. Its encoding is
+/// platform specific (eg: on Unix it is the raw wait status, which may also
+/// describe a signal rather than a code), so the same outcome could hash
+/// differently across platforms. Convert it with ExitStatus::code() instead.
+/// This is not std::process::ExitCode, which has no way to read the code back.
+/// Hashes the same as the i32.
+///
+/// ```
+/// use stable_hash::fast_stable_hash;
+/// use stable_hash::utils::ExitCode;
+///
+/// let failed = ExitCode(1);
+/// assert_eq!(fast_stable_hash(&failed), fast_stable_hash(&1i32));
+/// // Success is 0, which is the default
+/// assert_eq!(fast_stable_hash(&ExitCode(0)), fast_stable_hash(&()));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ExitCode(pub i32);
+
+impl StableHash for ExitCode {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        self.0.stable_hash(field_address, state)
+    }
+}
+
+/// Hashes a value by its Display output, exactly as the &str from to_string()
+/// would be hashed. This is a last resort for foreign types which implement
+/// Display but not StableHash.
+///
+/// Warning: this is fragile. Display output is meant for humans, and is not
+/// guaranteed to be stable. A new version of the type, its crate, or even the
+/// std lib may change the formatting, and with it every hash. Prefer
+/// implementing StableHash on the structure of the value whenever possible.
+///
+/// The output is formatted each time the value is hashed, which allocates.
+pub struct ViaDisplay<T>(pub T);
+
+impl<T: std::fmt::Display> StableHash for ViaDisplay<T> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        self.0
+            .to_string()
+            .as_str()
+            .stable_hash(field_address, state)
+    }
+}
+
+/// A value of a type whose schema has version V. Bumping V changes the hash of
+/// every value of the type, and of everything which contains it, so that hashes
+/// derived under an old schema are invalidated.
+///
+/// For any V other than 0, the value is hashed at child(2^63 + V) of the field
+/// address. Positional fields use small numbers and named fields use numbers
+/// below 2^63, so this cannot collide with a field. V must be below 2^63, or
+/// it would alias a lower version, and hashing fails to compile otherwise.
+/// Version 0 is the baseline, and hashes exactly like the value itself, so a
+/// type can start being versioned without changing its hash.
+///
+/// This differs from fast_stable_hash_versioned in two ways. The version is part
+/// of the type, so it applies wherever the value is nested rather than only at
+/// the root. And fast_stable_hash_versioned hashes the tuple (value, version),
+/// so its result differs from the plain hash even for version 0.
+pub struct Versioned<const V: u64, T>(pub T);
+
+impl<const V: u64, T> Versioned<V, T> {
+    const VERSION_IN_RANGE: () = assert!(V < 1 << 63, "The version must be below 2^63");
+}
+
+impl<const V: u64, T: StableHash> StableHash for Versioned<V, T> {
+    const TYPE_ID: Option<u64> = T::TYPE_ID;
+    const IS_UNIT: bool = T::IS_UNIT;
+
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        let () = Self::VERSION_IN_RANGE;
+        if V == 0 {
+            self.0.stable_hash(field_address, state)
+        } else {
+            self.0
+                .stable_hash(field_address.child((1 << 63) | V), state)
+        }
+    }
+}
+
+#[inline]
+fn trim_zeros(bytes: &[u8]) -> &[u8] {
+    profile_fn!(trim_zeros);
+
+    let mut end = bytes.len();
+    while end != 0 && bytes[end - 1] == 0 {
+        end -= 1;
+    }
+    &bytes[0..end]
+}
+
+/// Canonical way to write an integer of any size.
+///
+/// Backward compatibility:
+/// * The value +0 never writes bytes to the stream.
+/// * Integers of any size (u8..u24..u128...uN) are written in a canonical form, and can be written in any order.
+pub struct AsInt<'a> {
+    pub is_negative: bool,
+    pub little_endian: &'a [u8],
+}
+
+impl StableHash for AsInt<'_> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        // Having the negative sign be a child makes it possible to change the schema
+        // from u32 to i64 in a backward compatible way.
+        // This is also allowing for negative 0, like float, which is not used by
+        // any standard impl but may be used by some types.
+        if self.is_negative {
+            state.write(field_address.child(0), &[]);
+        }
+        let canon = trim_zeros(self.little_endian);
+        if !canon.is_empty() {
+            state.write(field_address, canon);
+        }
+    }
+}
+
+/// Declares which value of a type is its default for the purposes of hashing.
+/// Default values do not contribute to the hash, which allows fields to be
+/// added to a struct in a backward compatible way. The types in this crate
+/// skip their defaults (eg: 0, false, None, and empty collections) on their own.
+/// This trait allows other types to participate via skip_if_default.
+pub trait HasDefault {
+    fn is_default(&self) -> bool;
+}
+
+/// Hashes the value, unless it is the default in which case nothing is written.
+pub fn skip_if_default<T: StableHash + HasDefault, H: StableHasher>(
+    value: &T,
+    field_address: H::Addr,
+    state: &mut H,
+) {
+    profile_fn!(skip_if_default);
+
+    if !value.is_default() {
+        value.stable_hash(field_address, state)
+    }
+}
+
+pub(crate) fn generic_stable_hash<T: StableHash, H: StableHasher>(value: &T) -> H::Out {
+    let mut hasher = H::new();
+    root_stable_hash(value, H::Addr::root(), &mut hasher);
+    hasher.finish()
+}
+
+/// Hashes the value as the root of a hash, which is at root().child(TYPE_ID)
+/// when the type has one. See also StableHash::TYPE_ID
+pub(crate) fn root_stable_hash<T: StableHash, H: StableHasher>(
+    value: &T,
+    root: H::Addr,
+    state: &mut H,
+) {
+    let field_address = match T::TYPE_ID {
+        Some(type_id) => root.child(type_id),
+        None => root,
+    };
+    value.stable_hash(field_address, state);
+}
+
+// TODO: Create unit tests where this should fail
+pub fn check_for_child_errors<T: StableHash>(value: &T) -> Result<(), (ChildErr, Vec<PathItem>)> {
+    profile_fn!(check_for_child_errors);
+    generic_stable_hash::<T, crate::verification::ChildChecker>(value)
+}
@@ -0,0 +1,45 @@
+use crate::prelude::*;
+
+/// A sequence of bools packed into bytes, rather than hashed element-wise.
+///
+/// A [bool] hashes each element at child(i), writing an empty payload for true
+/// and nothing for false, followed by the length. Instead, this packs the bools
+/// as described in packed_bits_stable_hash. This is the same as the hash of a
+/// BitVec with the same bits (behind the bitvec feature), but not the same as
+/// the hash of the [bool]. An empty slice is the default and writes nothing.
+pub struct PackedBools<'a>(pub &'a [bool]);
+
+impl StableHash for PackedBools<'_> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        profile_method!(stable_hash);
+
+        packed_bits_stable_hash(self.0.iter().copied(), field_address, state)
+    }
+}
+
+/// The bits are packed into bytes in index order, with bit i stored at bit i % 8
+/// of byte i / 8, and written as a single AsBytes at child(0). The number of bits
+/// is then written at the parent address as an int. Any unused bits of the last
+/// byte are 0, and since the length is written, trailing 0 bits are not lost.
+/// eg: [1] and [1, 0] differ. No bits is the default and writes nothing.
+pub(crate) fn packed_bits_stable_hash<H: StableHasher>(
+    bits: impl Iterator<Item = bool>,
+    field_address: H::Addr,
+    state: &mut H,
+) {
+    profile_fn!(packed_bits_stable_hash);
+
+    let mut packed = Vec::new();
+    let mut len = 0u64;
+    for bit in bits {
+        if len % 8 == 0 {
+            packed.push(0u8);
+        }
+        if bit {
+            *packed.last_mut().unwrap() |= 1 << (len % 8);
+        }
+        len += 1;
+    }
+    AsBytes(&packed).stable_hash(field_address.child(0), state);
+    len.stable_hash(field_address, state);
+}
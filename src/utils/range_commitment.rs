@@ -0,0 +1,31 @@
+use crate::crypto::CryptoStableHasher;
+use crate::impls::root_map_entry;
+use crate::prelude::*;
+use std::collections::BTreeMap;
+use std::ops::RangeBounds;
+
+/// A commitment to only the entries of the map with keys in the range, using the
+/// crypto backend.
+///
+/// Commitments over non-overlapping ranges combine with mixin, in any order. When
+/// the ranges cover every key, finish() of the combined commitment is the same as
+/// crypto_stable_hash of the whole map. This makes it possible to show that a
+/// contiguous range of keys was included in a map given only the commitment to
+/// the range and a commitment to the rest.
+///
+/// The commitment is returned as a hasher rather than a finished [u8; 32], since a
+/// finished digest can no longer be combined. Call finish() for the 32 byte form.
+pub fn range_commitment<K, V, R>(map: &BTreeMap<K, V>, range: R) -> CryptoStableHasher
+where
+    K: StableHash + Ord,
+    V: StableHash,
+    R: RangeBounds<K>,
+{
+    profile_fn!(range_commitment);
+
+    let mut commitment = CryptoStableHasher::new();
+    for (key, value) in map.range(range) {
+        commitment.mixin(&root_map_entry(key, value));
+    }
+    commitment
+}
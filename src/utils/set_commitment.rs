@@ -0,0 +1,79 @@
+use crate::prelude::*;
+use std::collections::HashSet;
+
+/// The number of bits in a SetCommitment
+const BITS: usize = 2048;
+
+/// The number of bits set for each member
+const PROBES: usize = 4;
+
+/// A compact, approximate commitment to the members of a set, as a Bloom filter
+/// of BITS bits. See commit_set
+///
+/// Each member sets PROBES bits, chosen by splitting its 32 byte crypto hash into
+/// four little endian u64s and taking each modulo BITS. Setting a bit does not
+/// depend on which bits are already set, so the commitment depends only on the
+/// members of the set and not on the order in which they were inserted.
+///
+/// might_contain is never false for a member, but may be true for a value which
+/// is not a member. The chance of that grows with the number of members, to
+/// about 2% at 256 members and 50% at 1000.
+///
+/// The commitment relies on the collision resistance of crypto_stable_hash of
+/// each member. Values with the same crypto hash (eg: values which differ only
+/// in default fields, or the known collision described at crypto_stable_hash)
+/// set the same bits, so they are the same member as far as the commitment can
+/// tell. Otherwise, assuming the hashes of distinct values are indistinguishable
+/// from random, finding a value which might_contain accepts but is not a member
+/// takes about as many attempts as the inverse of the false positive rate above.
+/// That is few for a large set, so this is a filter rather than a binding
+/// commitment against a peer who chooses values to fool it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SetCommitment {
+    bits: [u8; BITS / 8],
+}
+
+/// Commits to the members of the set. See SetCommitment
+pub fn commit_set<T: StableHash, S>(set: &HashSet<T, S>) -> SetCommitment {
+    profile_fn!(commit_set);
+
+    let mut commitment = SetCommitment {
+        bits: [0; BITS / 8],
+    };
+    for member in set {
+        for bit in probes(member) {
+            commitment.bits[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+    commitment
+}
+
+impl SetCommitment {
+    /// Whether the value may be a member of the set. False only if it is not.
+    pub fn might_contain<T: StableHash>(&self, value: &T) -> bool {
+        profile_method!(might_contain);
+
+        probes(value)
+            .into_iter()
+            .all(|bit| self.bits[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+
+    /// The bits of the filter, with bit i at bit i % 8 of byte i / 8. eg: to send
+    /// to a peer.
+    pub fn as_bytes(&self) -> &[u8; BITS / 8] {
+        &self.bits
+    }
+
+    pub fn from_bytes(bits: [u8; BITS / 8]) -> Self {
+        Self { bits }
+    }
+}
+
+fn probes<T: StableHash>(value: &T) -> [usize; PROBES] {
+    let hash = crate::crypto_stable_hash(value);
+    let mut probes = [0; PROBES];
+    for (probe, word) in probes.iter_mut().zip(hash.chunks_exact(8)) {
+        *probe = (u64::from_le_bytes(word.try_into().unwrap()) % BITS as u64) as usize;
+    }
+    probes
+}
@@ -0,0 +1,24 @@
+use crate::prelude::*;
+use std::collections::HashMap;
+
+/// A digest of the map computed by sorting its entries rather than by combining
+/// them as an unordered collection, for interop with implementations which
+/// compute the hash of a map that way. Neither K nor V needs to be Ord, since the
+/// entries are sorted by their hashes.
+///
+/// Each entry is hashed as crypto_stable_hash of the (key, value) tuple. The 32
+/// byte entry hashes are sorted in ascending order, and the digest is
+/// crypto_stable_hash of a Vec of them, each hashed as AsBytes. So, it depends
+/// only on the entries and not on their order in the map. It differs from
+/// crypto_stable_hash of the map.
+pub fn sorted_by_hash_digest<K: StableHash, V: StableHash, S>(map: &HashMap<K, V, S>) -> [u8; 32] {
+    profile_fn!(sorted_by_hash_digest);
+
+    let mut entries: Vec<[u8; 32]> = map
+        .iter()
+        .map(|entry| crate::crypto_stable_hash(&entry))
+        .collect();
+    entries.sort_unstable();
+    let entries: Vec<AsBytes> = entries.iter().map(|entry| AsBytes(entry)).collect();
+    crate::crypto_stable_hash(&entries)
+}
@@ -1,7 +1,8 @@
 use {
     crate::prelude::*,
     std::{
-        collections::HashSet,
+        collections::{BTreeMap, HashSet},
+        fmt,
         sync::{
             atomic::{AtomicU8, Ordering::SeqCst},
             Arc, Mutex,
@@ -9,8 +10,10 @@ use {
     },
 };
 
+type SharedErr = Arc<Mutex<Option<(ChildErr, Vec<PathItem>)>>>;
+
 pub struct ChildState {
-    err: Arc<Mutex<Option<(ChildErr, Vec<PathItem>)>>>,
+    err: SharedErr,
     children: Mutex<HashSet<u64>>,
     state: AtomicU8,
     path: Vec<PathItem>,
@@ -26,7 +29,7 @@ pub struct ChildChecker {
     err: Option<(ChildErr, Vec<PathItem>)>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PathItem {
     UnorderedRoot,
     UnorderedResult,
@@ -141,6 +144,14 @@ impl StableHasher for ChildChecker {
             },
         );
     }
+    fn write_chunks<'a>(
+        &mut self,
+        field_address: Self::Addr,
+        _chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) {
+        // Only the address is checked, so the chunks are never read
+        self.write(field_address, &[]);
+    }
     fn mixin(&mut self, _other: &Self) {
         unimplemented!()
     }
@@ -163,3 +174,390 @@ impl StableHasher for ChildChecker {
         todo!()
     }
 }
+
+/// The path taken from the root to reach a field.
+///
+/// Displays as the child numbers separated by '.', with {} standing in for
+/// the members of an unordered collection. The root displays as '$'.
+/// eg: "$.0.2" is the third field of the first field.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FieldPath(pub Vec<PathItem>);
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("$")?;
+        for item in &self.0 {
+            match item {
+                PathItem::Child(number) => write!(f, ".{}", number)?,
+                PathItem::UnorderedResult => f.write_str(".{}")?,
+                PathItem::UnorderedRoot => f.write_str(".{member}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FieldAddress for FieldPath {
+    fn root() -> Self {
+        Self(Vec::new())
+    }
+    fn child(&self, number: u64) -> Self {
+        self.with(PathItem::Child(number))
+    }
+    fn unordered(&self) -> (Self, Self) {
+        (
+            self.with(PathItem::UnorderedRoot),
+            self.with(PathItem::UnorderedResult),
+        )
+    }
+}
+
+impl FieldPath {
+    fn with(&self, item: PathItem) -> Self {
+        let mut path = self.0.clone();
+        path.push(item);
+        Self(path)
+    }
+}
+
+/// Records every write rather than hashing, so that the encoding of a value
+/// can be inspected.
+///
+/// Members of unordered collections are traced in their own Tracer, and
+/// appear in the parent as an opaque serialization of that trace written at
+/// the collection's unordered result path.
+pub struct Tracer {
+    fields: Vec<(FieldPath, Vec<u8>)>,
+}
+
+impl StableHasher for Tracer {
+    /// Every write, sorted by path
+    type Out = Vec<(FieldPath, Vec<u8>)>;
+    type Addr = FieldPath;
+    type Bytes = Vec<u8>;
+
+    fn new() -> Self {
+        Self { fields: Vec::new() }
+    }
+    fn write(&mut self, field_address: Self::Addr, bytes: &[u8]) {
+        self.fields.push((field_address, bytes.to_vec()));
+    }
+    fn write_chunks<'a>(
+        &mut self,
+        field_address: Self::Addr,
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) {
+        // The trace records every payload, so the chunks are necessarily collected
+        let mut bytes = Vec::new();
+        for chunk in chunks {
+            bytes.extend_from_slice(chunk);
+        }
+        self.fields.push((field_address, bytes));
+    }
+    fn mixin(&mut self, other: &Self) {
+        self.fields.extend(other.fields.iter().cloned());
+    }
+    fn unmix(&mut self, other: &Self) {
+        for field in &other.fields {
+            let index = self
+                .fields
+                .iter()
+                .position(|mine| mine == field)
+                .expect("Unmixed a field which was never mixed in");
+            self.fields.swap_remove(index);
+        }
+    }
+    fn finish(&self) -> Self::Out {
+        let mut fields = self.fields.clone();
+        fields.sort();
+        fields
+    }
+    fn to_bytes(&self) -> Self::Bytes {
+        // Length prefixed, so that distinct traces have distinct serializations
+        let mut bytes = Vec::new();
+        for (path, payload) in self.finish() {
+            bytes.extend_from_slice(&(path.0.len() as u64).to_le_bytes());
+            for item in path.0 {
+                let (tag, number) = match item {
+                    PathItem::UnorderedRoot => (0, 0),
+                    PathItem::UnorderedResult => (1, 0),
+                    PathItem::Child(number) => (2, number),
+                };
+                bytes.push(tag);
+                bytes.extend_from_slice(&number.to_le_bytes());
+            }
+            bytes.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&payload);
+        }
+        bytes
+    }
+    fn from_bytes(bytes: Self::Bytes) -> Self {
+        fn take<'a>(bytes: &mut &'a [u8], len: usize) -> &'a [u8] {
+            assert!(bytes.len() >= len, "Truncated trace");
+            let (head, tail) = bytes.split_at(len);
+            *bytes = tail;
+            head
+        }
+        fn take_u64(bytes: &mut &[u8]) -> u64 {
+            u64::from_le_bytes(take(bytes, 8).try_into().unwrap())
+        }
+
+        let mut fields = Vec::new();
+        let mut bytes = &bytes[..];
+        while !bytes.is_empty() {
+            let path_len = take_u64(&mut bytes);
+            let mut path = Vec::new();
+            for _ in 0..path_len {
+                let tag = take(&mut bytes, 1)[0];
+                let number = take_u64(&mut bytes);
+                path.push(match tag {
+                    0 => PathItem::UnorderedRoot,
+                    1 => PathItem::UnorderedResult,
+                    2 => PathItem::Child(number),
+                    _ => panic!("Unknown path item tag {}", tag),
+                });
+            }
+            let payload_len = take_u64(&mut bytes) as usize;
+            let payload = take(&mut bytes, payload_len).to_vec();
+            fields.push((FieldPath(path), payload));
+        }
+        Self { fields }
+    }
+}
+
+/// The canonical encoding of every write made when hashing the value. Two values
+/// have the same encoding exactly when they write the same payloads at the same
+/// paths, and so hash the same with every backend. This spells out the encoding
+/// for other implementations (eg: in another language), which can compute these
+/// bytes to check that they agree with this crate on what is written.
+///
+/// This is the exact preimage to hash when comparing implementations: any two
+/// which compute this byte string and apply xxh3 to it get the same result.
+/// Note that the backends of this crate do not hash a single byte string. They
+/// hash every write separately, and combine the results in an order independent
+/// way, so eg: fast_stable_hash is not xxh3 of these bytes.
+///
+/// The writes are sorted by path, then by payload. Paths are compared item by
+/// item as (tag, number) pairs (see below), with a path ordered before any
+/// longer path which it is a prefix of. Payloads are compared as bytes, in the
+/// same way. Each write is encoded as:
+///  * The number of items in the path, as a u64
+///  * Each item of the path, as a tag byte followed by a u64 number: 2 and the
+///    number for child(number), 0 and 0 for the member of an unordered
+///    collection, and 1 and 0 for the result of an unordered collection
+///  * The length of the payload, as a u64
+///  * The payload
+///
+/// Every u64 is little endian. Each member of an unordered collection is encoded
+/// on its own in this way, and the result is written as the payload at the
+/// result path of the collection. So a member which writes nothing is written
+/// as an empty payload. Since the writes are sorted, the order in which the
+/// members are visited does not matter.
+#[cfg(feature = "trace")]
+pub fn canonical_encoding<T: StableHash>(value: &T) -> Vec<u8> {
+    profile_fn!(canonical_encoding);
+
+    let mut tracer = Tracer::new();
+    root_stable_hash(value, FieldPath::root(), &mut tracer);
+    tracer.to_bytes()
+}
+
+/// A deterministic JSON document describing what each entry of the map
+/// contributes to its hash, eg: to be checked in as a golden file and compared
+/// against other implementations. The document is the same for any two maps
+/// with the same entries, regardless of the order in which they were inserted.
+///
+/// Each entry is given as the crypto hash of its key and of its value, in hex,
+/// and the entries are sorted by those hashes. The crypto hash of the whole map
+/// is given as well. eg:
+///
+/// ```text
+/// {
+///   "hash": "<64 hex digits>",
+///   "entries": [
+///     {"key": "<64 hex digits>", "value": "<64 hex digits>"}
+///   ]
+/// }
+/// ```
+///
+/// The layout, including whitespace, is stable. Each entry is on its own line,
+/// so that a change to one entry changes one line of the golden file.
+#[cfg(feature = "trace")]
+pub fn map_golden_json<K: StableHash, V: StableHash, S>(
+    map: &std::collections::HashMap<K, V, S>,
+) -> String {
+    profile_fn!(map_golden_json);
+
+    fn hex(bytes: [u8; 32]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    let mut entries: Vec<_> = map
+        .iter()
+        .map(|(key, value)| {
+            (
+                hex(crate::crypto_stable_hash(key)),
+                hex(crate::crypto_stable_hash(value)),
+            )
+        })
+        .collect();
+    entries.sort_unstable();
+
+    let entries: Vec<String> = entries
+        .into_iter()
+        .map(|(key, value)| format!("    {{\"key\": \"{}\", \"value\": \"{}\"}}", key, value))
+        .collect();
+    let entries = if entries.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n  ]", entries.join(",\n"))
+    };
+    format!(
+        "{{\n  \"hash\": \"{}\",\n  \"entries\": {}\n}}\n",
+        hex(crate::crypto_stable_hash(map)),
+        entries
+    )
+}
+
+/// Returns the path of every field where the encodings of a and b differ,
+/// including fields that were written for only one of them.
+///
+/// Default values write nothing, so a field that changed to or from its
+/// default is reported like any other. When any member of an unordered
+/// collection differs, the collection is reported as a whole at its
+/// unordered result path (eg: "$.1.{}").
+pub fn diff_fields<T: StableHash>(a: &T, b: &T) -> Vec<FieldPath> {
+    profile_fn!(diff_fields);
+
+    fn trace<T: StableHash>(value: &T) -> BTreeMap<FieldPath, Vec<Vec<u8>>> {
+        let mut fields = BTreeMap::new();
+        for (path, payload) in generic_stable_hash::<T, Tracer>(value) {
+            fields.entry(path).or_insert_with(Vec::new).push(payload);
+        }
+        fields
+    }
+
+    let a = trace(a);
+    let mut b = trace(b);
+
+    let mut diff = Vec::new();
+    for (path, payloads) in a {
+        if b.remove(&path).as_ref() != Some(&payloads) {
+            diff.push(path);
+        }
+    }
+    diff.extend(b.into_keys());
+    diff.sort();
+    diff
+}
+
+/// Returns (the fast hash of the schema of the value, the fast hash of the value).
+///
+/// The schema is the set of paths which were written, with the payloads ignored.
+/// So, two values which differ only in their data share the first component,
+/// and a consumer can reject a comparison between values with different schemas
+/// without comparing the data. The second component is fast_stable_hash.
+///
+/// Since default values write nothing, a field which is default in one value
+/// but not the other is part of the schema of only one of them. The members of
+/// an unordered collection are opaque, so only the path of the collection is
+/// part of the schema, regardless of the members or their number.
+pub fn schema_and_data_hash<T: StableHash>(value: &T) -> (u128, u128) {
+    profile_fn!(schema_and_data_hash);
+
+    let mut paths: Vec<Vec<(u8, u64)>> = generic_stable_hash::<T, Tracer>(value)
+        .into_iter()
+        .map(|(path, _)| {
+            path.0
+                .into_iter()
+                .map(|item| match item {
+                    PathItem::UnorderedRoot => (0, 0),
+                    PathItem::UnorderedResult => (1, 0),
+                    PathItem::Child(number) => (2, number),
+                })
+                .collect()
+        })
+        .collect();
+    // Already sorted by the tracer
+    paths.dedup();
+    (
+        crate::fast_stable_hash(&paths),
+        crate::fast_stable_hash(value),
+    )
+}
+
+/// The number of final output collisions among a sample of values.
+/// See collision_stats
+#[cfg(feature = "test-utils")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollisionReport {
+    pub samples: usize,
+    /// The number of samples whose hash matched that of an earlier sample
+    pub collisions: usize,
+}
+
+#[cfg(feature = "test-utils")]
+impl CollisionReport {
+    /// The fraction of samples which collided
+    pub fn rate(&self) -> f64 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+        self.collisions as f64 / self.samples as f64
+    }
+}
+
+/// Hashes n values from gen with fast_stable_hash, and counts the collisions
+/// in the final output. This is for tracking the collision rate of the fast
+/// backend over time, which unlike the crypto backend makes no guarantee.
+///
+/// Values which are equal hash the same, and are counted as collisions. So,
+/// gen should produce values from a large enough space that repeats are rare.
+#[cfg(feature = "test-utils")]
+pub fn collision_stats<T: StableHash, F: Fn() -> T>(gen: F, n: usize) -> CollisionReport {
+    profile_fn!(collision_stats);
+
+    let mut seen = HashSet::with_capacity(n);
+    let mut collisions = 0;
+    for _ in 0..n {
+        if !seen.insert(crate::fast_stable_hash(&gen())) {
+            collisions += 1;
+        }
+    }
+    CollisionReport {
+        samples: n,
+        collisions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tracer;
+    use crate::prelude::*;
+    use std::collections::HashSet;
+
+    fn trace<T: StableHash>(value: &T) -> Tracer {
+        let mut tracer = Tracer::new();
+        value.stable_hash(FieldPath::root(), &mut tracer);
+        tracer
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let value = (vec![1u32, 0, 3], HashSet::from([2u8, 5]), "text");
+        let tracer = trace(&value);
+        let parsed = Tracer::from_bytes(tracer.to_bytes());
+        assert_eq!(tracer.finish(), parsed.finish());
+        assert!(Tracer::from_bytes(Vec::new()).finish().is_empty());
+    }
+
+    #[test]
+    fn unmix_reverses_mixin() {
+        let a = trace(&(1u32, 2u32));
+        let b = trace(&(1u32, 3u32));
+        let mut mixed = trace(&(1u32, 2u32));
+        mixed.mixin(&b);
+        mixed.unmix(&b);
+        assert_eq!(a.finish(), mixed.finish());
+    }
+}
@@ -0,0 +1,31 @@
+mod common;
+
+#[test]
+fn matches_vec() {
+    equal!(
+        99946908715907655724842348751554312075, "25dfaa9f92a3f2b05a1bdfbc66ec594c545dc39ebdb0e9ae769350ea1726e2b7";
+        [1u32, 2u32],
+        &[1u32, 2u32],
+        &[1u32, 2u32][..],
+        vec![1u32, 2u32]
+    );
+}
+
+#[test]
+fn byte_array_reference() {
+    // Eg: a key passed around as &[u8; 32]
+    let key = [7u8; 32];
+    let by_ref: &[u8; 32] = &key;
+    equal!(
+        4939881557993135187977256505540445655, "0c9cac7c556f217b3fd935688a1bfd96c5929aeccb5ce59555963821da77631f";
+        by_ref,
+        key,
+        key.to_vec()
+    );
+}
+
+#[test]
+fn trailing_defaults_are_counted() {
+    not_equal!([true, false], [true, false, false]);
+    not_equal!([0u32; 0], [0u32; 1]);
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "arrayvec")]
+
+mod common;
+
+use arrayvec::ArrayVec;
+
+#[test]
+fn full_matches_vec() {
+    let full: ArrayVec<u32, 3> = [1, 2, 3].into_iter().collect();
+    assert!(full.is_full());
+    equal!(
+        193833936596083547035584674840075484437, "f06836982af48669f211bf9c60bd27f8326540d528bd8ea9fda5f25d8b283a54";
+        full,
+        vec![1u32, 2, 3],
+        &[1u32, 2, 3][..]
+    );
+}
+
+#[test]
+fn partial_matches_vec() {
+    let mut partial: ArrayVec<u32, 8> = ArrayVec::new();
+    partial.push(1);
+    partial.push(2);
+    partial.push(3);
+    equal!(
+        193833936596083547035584674840075484437, "f06836982af48669f211bf9c60bd27f8326540d528bd8ea9fda5f25d8b283a54";
+        partial,
+        vec![1u32, 2, 3]
+    );
+    not_equal!(partial, vec![1u32, 2, 3, 0]);
+}
+
+#[test]
+fn empty_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        ArrayVec::<u32, 4>::new(),
+        Vec::<u32>::new()
+    );
+}
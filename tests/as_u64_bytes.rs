@@ -0,0 +1,43 @@
+mod common;
+
+use stable_hash::utils::{AsBytes, AsU64Bytes};
+
+fn element_wise(words: &[u64]) -> Vec<u8> {
+    words.iter().flat_map(|w| w.to_le_bytes()).collect()
+}
+
+#[test]
+fn matches_element_wise_bytes() {
+    let words: Vec<u64> = (0..1000u64)
+        .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        .collect();
+    let bytes = element_wise(&words);
+    assert_eq!(
+        common::fast_stable_hash(&AsU64Bytes(&words)),
+        common::fast_stable_hash(&AsBytes(&bytes))
+    );
+    assert_eq!(
+        common::crypto_stable_hash_str(&AsU64Bytes(&words)),
+        common::crypto_stable_hash_str(&AsBytes(&bytes))
+    );
+}
+
+#[test]
+fn pinned() {
+    let buffer: Vec<u64> = vec![1, 0x0102_0304_0506_0708];
+    equal!(
+        40808999985215597189038027730542617154, "7c450e6d90726e0a092f8c8891273eb6f3d51cd09c3c1738ec6d081be87830a6";
+        AsU64Bytes(&[1, 0x0102_0304_0506_0708]),
+        AsU64Bytes(&buffer),
+        AsBytes(&[1, 0, 0, 0, 0, 0, 0, 0, 8, 7, 6, 5, 4, 3, 2, 1])
+    );
+}
+
+#[test]
+fn empty_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        AsU64Bytes(&[]),
+        AsBytes(&[])
+    );
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicIsize, AtomicU32, AtomicUsize, Ordering};
+
+// usize is 32 bits on some targets and 64 on others. Hashing the same as both
+// u32 and u64 pins that the hash of AtomicUsize does not depend on the target.
+#[test]
+fn atomic_usize_is_platform_independent() {
+    equal!(
+        142312350356870347133330573639367315867, "5c7ee97b123d8d723abb6e470ce17edbea2de55891b76927d1255c7881880615";
+        AtomicUsize::new(5),
+        AtomicU32::new(5),
+        5usize,
+        5u32,
+        5u64
+    );
+}
+
+#[test]
+fn atomic_isize_is_platform_independent() {
+    equal!(
+        160587122640522419566007472896962896280, "1744aa06235c0fd389a0bbe0cfd0b96707fe2c941956e20c95b42acc489ef753";
+        AtomicIsize::new(-5),
+        AtomicI64::new(-5),
+        -5isize,
+        -5i32,
+        -5i64
+    );
+}
+
+#[test]
+fn atomic_defaults() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        AtomicUsize::new(0),
+        AtomicBool::new(false),
+        0u32
+    );
+}
+
+#[test]
+fn hashes_current_value() {
+    let value = AtomicUsize::new(1);
+    value.fetch_add(4, Ordering::SeqCst);
+    equal!(
+        142312350356870347133330573639367315867, "5c7ee97b123d8d723abb6e470ce17edbea2de55891b76927d1255c7881880615";
+        value,
+        5u8
+    );
+    not_equal!(AtomicBool::new(true), AtomicBool::new(false));
+}
@@ -1,5 +1,6 @@
 use stable_hash::prelude::*;
-use stable_hash::utils::AsBytes;
+use stable_hash::utils::{skip_if_default, AsBytes, HasDefault};
+use std::collections::{BTreeMap, HashMap, HashSet};
 mod common;
 
 struct One<T0> {
@@ -34,6 +35,24 @@ fn add_optional_field() {
     equal!(102568403942768160221811810082933398928, "3428a4134bfdac56aa04614504705b0ffd1d48f27777b109a793e5a641324212"; one, two);
 }
 
+// Tuple elements are addressed like struct fields
+#[test]
+fn tuple_matches_struct() {
+    let one = One { one: 5u32 };
+    equal!(102568403942768160221811810082933398928, "3428a4134bfdac56aa04614504705b0ffd1d48f27777b109a793e5a641324212"; one, (5u32,));
+    let two = Two {
+        one: 5u32,
+        two: "two",
+    };
+    equal!(132362099246073167487351295751252569864, "ac1c51e28cd13dd79e5a6b9beadf1091ae987863cceb262b5e0f1387dc945e81"; two, (5u32, "two"));
+}
+
+#[test]
+fn tuple_add_default_element() {
+    equal!(102568403942768160221811810082933398928, "3428a4134bfdac56aa04614504705b0ffd1d48f27777b109a793e5a641324212"; (5u32,), (5u32, 0u32), (5u32, 0u32, Option::<u8>::None));
+    not_equal!((5u32,), (5u32, 1u32));
+}
+
 #[test]
 fn add_default_field() {
     let one = One { one: "one" };
@@ -69,6 +88,26 @@ fn empty_vec_is_default() {
     equal!(338065623630761276859032195206974584513, "db4657c873e33a60e581eb5458aba6c76f510e023872c76a3134608619342c59"; one, two);
 }
 
+#[test]
+fn empty_hash_map_is_default() {
+    let one = One { one: true };
+    let two = Two {
+        one: true,
+        two: HashMap::<u32, String>::new(),
+    };
+    equal!(338065623630761276859032195206974584513, "db4657c873e33a60e581eb5458aba6c76f510e023872c76a3134608619342c59"; one, two);
+}
+
+#[test]
+fn empty_hash_set_is_default() {
+    let one = One { one: true };
+    let two = Two {
+        one: true,
+        two: HashSet::<u32>::new(),
+    };
+    equal!(338065623630761276859032195206974584513, "db4657c873e33a60e581eb5458aba6c76f510e023872c76a3134608619342c59"; one, two);
+}
+
 #[test]
 fn two_is_used() {
     let one = One { one: true };
@@ -91,6 +130,7 @@ fn last_default_does_not_collide() {
 }
 
 #[test]
+#[allow(clippy::useless_vec)]
 fn as_bytes() {
     let v = vec![0u8];
     not_equal!(&v[..], AsBytes(&v[..]));
@@ -99,6 +139,25 @@ fn as_bytes() {
     not_equal!(&v[..], AsBytes(&v[..]));
 }
 
+#[test]
+fn boxed_slice_matches_vec() {
+    let boxed: Box<[u32]> = vec![1u32, 2u32].into_boxed_slice();
+    equal!(
+        99946908715907655724842348751554312075, "25dfaa9f92a3f2b05a1bdfbc66ec594c545dc39ebdb0e9ae769350ea1726e2b7";
+        boxed,
+        vec![1u32, 2u32],
+        &[1u32, 2u32][..]
+    );
+
+    let empty: Box<[u32]> = Box::new([]);
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        empty,
+        Vec::<u32>::new(),
+        &[0u32; 0][..]
+    );
+}
+
 #[test]
 fn numbers_through_vec() {
     equal!(
@@ -107,3 +166,151 @@ fn numbers_through_vec() {
         vec![1u16, 2u16]
     );
 }
+
+#[test]
+fn result_default_payloads_ne() {
+    not_equal!(
+        Result::<Vec<u32>, String>::Ok(vec![]),
+        Result::<Vec<u32>, String>::Err("".to_string())
+    );
+}
+
+#[test]
+fn result_default_payload_is_not_absent() {
+    let one = One { one: 5u32 };
+    let two = Two {
+        one: 5u32,
+        two: Result::<Vec<u32>, String>::Ok(vec![]),
+    };
+    not_equal!(one, two);
+}
+
+#[test]
+fn result_ok() {
+    equal!(75519865775010932100351859059316031773, "da631c85f6197fe9f10ed8c09254242e0089518449bd595556805ae656df17c2"; Result::<u32, u32>::Ok(5));
+}
+
+// See also 33a9b3bf-0d43-4fd0-a3ed-a77807505255
+#[test]
+fn slice_length_does_not_collide_with_child_field() {
+    // The length 3 is written at the parent, whereas the field is at child(0)
+    not_equal!(vec![0u32, 0u32, 0u32], One { one: 3u32 });
+    // Same item at child(0), but only the slice writes a length
+    not_equal!(vec![5u32], One { one: 5u32 });
+    not_equal!(
+        Two {
+            one: vec![0u32],
+            two: 0u32
+        },
+        Two {
+            one: Vec::<u32>::new(),
+            two: 1u32
+        }
+    );
+}
+
+struct NamedXY {
+    x: u32,
+    y: &'static str,
+}
+
+impl StableHash for NamedXY {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        self.x.stable_hash(field_address.named_child("x"), state);
+        self.y.stable_hash(field_address.named_child("y"), state);
+    }
+}
+
+struct NamedYX {
+    y: &'static str,
+    x: u32,
+}
+
+impl StableHash for NamedYX {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        self.y.stable_hash(field_address.named_child("y"), state);
+        self.x.stable_hash(field_address.named_child("x"), state);
+    }
+}
+
+#[test]
+fn reorder_named_fields() {
+    let xy = NamedXY { x: 1, y: "y" };
+    let yx = NamedYX { y: "y", x: 1 };
+    equal!(212061543233551892369957990780768207951, "98b7d809eebc0244d084847f13932ae071a7016762735cef9f822abf7789376f"; xy, yx);
+}
+
+#[test]
+fn named_fields_ne() {
+    not_equal!(NamedXY { x: 1, y: "" }, NamedXY { x: 0, y: "\u{1}" });
+}
+
+/// A type whose default is not represented by 0
+#[derive(Clone, Copy)]
+enum Level {
+    Low = 1,
+    Normal = 2,
+    High = 3,
+}
+
+impl HasDefault for Level {
+    fn is_default(&self) -> bool {
+        matches!(self, Level::Normal)
+    }
+}
+
+impl StableHash for Level {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        state.write(field_address, &[*self as u8]);
+    }
+}
+
+struct WithLevel<T0> {
+    one: T0,
+    level: Level,
+}
+
+impl<T0: StableHash> StableHash for WithLevel<T0> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        self.one.stable_hash(field_address.child(0), state);
+        skip_if_default(&self.level, field_address.child(1), state);
+    }
+}
+
+#[test]
+fn add_custom_default_field() {
+    let one = One { one: 5u32 };
+    let two = WithLevel {
+        one: 5u32,
+        level: Level::Normal,
+    };
+    equal!(102568403942768160221811810082933398928, "3428a4134bfdac56aa04614504705b0ffd1d48f27777b109a793e5a641324212"; one, two);
+}
+
+#[test]
+fn custom_non_default_is_used() {
+    let one = One { one: 5u32 };
+    for level in [Level::Low, Level::High] {
+        let two = WithLevel { one: 5u32, level };
+        not_equal!(one, two);
+    }
+}
+
+// A map to () hashes as the set of its keys, rather than like a map whose values
+// are all default (which writes each key at child(0) of its entry). This changed
+// the hash of unit-valued maps. See CHANGELOG.md
+#[test]
+fn unit_valued_map_is_set() {
+    let keys = [1u32, 2, 3];
+    equal!(261168114195377271993952934537749440506, "4a87fcf3748ef16f7ebd64f1547d757a0b74c26d06a3368bcc03a8fce77734ef";
+        keys.iter().map(|k| (*k, ())).collect::<HashMap<_, _>>(),
+        keys.iter().map(|k| (*k, ())).collect::<BTreeMap<_, _>>(),
+        keys.iter().copied().collect::<HashSet<_>>()
+    );
+    not_equal!(
+        keys.iter().map(|k| (*k, ())).collect::<HashMap<_, _>>(),
+        keys.iter()
+            .map(|k| (*k, None::<u8>))
+            .collect::<HashMap<_, _>>()
+    );
+}
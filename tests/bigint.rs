@@ -0,0 +1,36 @@
+#![cfg(feature = "num-bigint")]
+
+mod common;
+
+use num_bigint::{BigInt, BigUint};
+
+#[test]
+fn big_uint_matches_fixed_width() {
+    equal!(142312350356870347133330573639367315867, "5c7ee97b123d8d723abb6e470ce17edbea2de55891b76927d1255c7881880615"; BigUint::from(5u32), BigInt::from(5i32), 5u32, 5u128);
+}
+
+#[test]
+fn big_int_negative_matches_fixed_width() {
+    equal!(335003237357266958123298067172023699924, "bd6bdf423f63e1f5135e56bfc4e9d84a709378adae16c295aaef4bb656024743"; BigInt::from(-300i32), -300i16, -300i128);
+}
+
+#[test]
+fn zero_is_default() {
+    equal!(
+        102568403942768160221811810082933398928, "3428a4134bfdac56aa04614504705b0ffd1d48f27777b109a793e5a641324212";
+        (5u32, BigUint::from(0u32)),
+        (5u32, BigInt::from(0i32)),
+        (5u32, 0u32)
+    );
+}
+
+#[test]
+fn beyond_u128() {
+    let big = BigUint::from(u128::MAX) + 2u32;
+    equal!(327277906709080638489319046596875216775, "2e71f51995c441b8f97579d7f5ef117d05dd6b0e23c1ad6be2f933f09f9bc257"; big, BigInt::from(u128::MAX) + 2i32);
+    not_equal!(BigUint::from(u128::MAX) + 2u32, BigUint::from(1u32));
+    not_equal!(
+        BigInt::from(u128::MAX) + 2i32,
+        -(BigInt::from(u128::MAX) + 2i32)
+    );
+}
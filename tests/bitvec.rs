@@ -0,0 +1,59 @@
+#![cfg(feature = "bitvec")]
+
+mod common;
+
+use bitvec::prelude::*;
+
+#[test]
+fn storage_does_not_matter() {
+    let bits = [
+        true, false, true, true, false, false, false, true, true, true, false,
+    ];
+    let lsb_u8: BitVec<u8, Lsb0> = bits.iter().copied().collect();
+    let msb_u8: BitVec<u8, Msb0> = bits.iter().copied().collect();
+    let lsb_u32: BitVec<u32, Lsb0> = bits.iter().copied().collect();
+    let msb_u64: BitVec<u64, Msb0> = bits.iter().copied().collect();
+    equal!(
+        114262880536433561658482469091101021317, "03562700a54ce887fd03cd0df3348ef8e90646f22af74c80005f25be800ca51e";
+        lsb_u8,
+        msb_u8,
+        lsb_u32,
+        msb_u64,
+        lsb_u8.as_bitslice()
+    );
+}
+
+#[test]
+fn unused_bits_do_not_matter() {
+    // The same 5 bits, but with different junk in the unused part of the word
+    let mut clean = BitVec::<u8, Lsb0>::from_element(0b0000_0101);
+    clean.truncate(5);
+    let mut dirty = BitVec::<u8, Lsb0>::from_element(0b1110_0101);
+    dirty.truncate(5);
+    assert_eq!(
+        common::fast_stable_hash(&clean),
+        common::fast_stable_hash(&dirty)
+    );
+}
+
+#[test]
+fn trailing_zeros_are_counted() {
+    let short = bitvec![u8, Lsb0; 1, 0, 1];
+    let long = bitvec![u8, Lsb0; 1, 0, 1, 0];
+    let word = bitvec![u8, Lsb0; 1, 0, 1, 0, 0, 0, 0, 0];
+    let over = bitvec![u8, Lsb0; 1, 0, 1, 0, 0, 0, 0, 0, 0];
+    not_equal!(short, long);
+    not_equal!(long, word);
+    not_equal!(word, over);
+    not_equal!(bitvec![u8, Lsb0; 0], bitvec![u8, Lsb0; 0, 0]);
+}
+
+#[test]
+fn empty_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        BitVec::<u8, Lsb0>::new(),
+        BitVec::<u64, Msb0>::new(),
+        ()
+    );
+}
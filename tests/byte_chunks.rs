@@ -0,0 +1,110 @@
+mod common;
+
+use stable_hash::utils::{byte_chunks_stable_hash, AsByteChunks, AsBytes};
+use stable_hash::{StableHash, StableHasher};
+
+#[test]
+fn chunked_matches_contiguous_100mb() {
+    let payload: Vec<u8> = (0..100 * 1024 * 1024u32)
+        .map(|i| (i.wrapping_mul(2654435761) >> 24) as u8)
+        .collect();
+    let fast = common::fast_stable_hash(&AsBytes(&payload));
+    let crypto = common::crypto_stable_hash_str(&AsBytes(&payload));
+
+    for chunk_size in [1 << 20, 1_000_003, 64 * 1024 * 1024] {
+        let chunked = AsByteChunks(payload.chunks(chunk_size));
+        assert_eq!(fast, common::fast_stable_hash(&chunked));
+        assert_eq!(crypto, common::crypto_stable_hash_str(&chunked));
+    }
+}
+
+#[test]
+fn chunked_matches_as_bytes() {
+    let payload = b"the quick brown fox jumps over the lazy dog";
+    let (left, right) = payload.split_at(10);
+    equal!(
+        46822073894057055978478298981629596873, "ea43c1b4f6985385a119710e0c5875a4c601240c0bf99f56c2f4d7d98c3fbc67";
+        AsBytes(payload),
+        AsByteChunks(payload.chunks(3)),
+        AsByteChunks([left, &[], right])
+    );
+}
+
+/// An iterator which is not Clone, so it can only be consumed once
+struct OneShot<I>(I);
+
+impl<I: Iterator> Iterator for OneShot<I> {
+    type Item = I::Item;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+/// Hashes its payload from a one shot iterator over pieces of it
+struct Streamed(&'static [u8]);
+
+impl StableHash for Streamed {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        byte_chunks_stable_hash(OneShot(self.0.chunks(4)), field_address, state)
+    }
+}
+
+#[test]
+fn one_shot_chunks_match_as_bytes() {
+    let payload = b"the quick brown fox jumps over the lazy dog";
+    equal!(
+        46822073894057055978478298981629596873, "ea43c1b4f6985385a119710e0c5875a4c601240c0bf99f56c2f4d7d98c3fbc67";
+        Streamed(payload),
+        AsBytes(payload)
+    );
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        Streamed(&[]),
+        AsBytes(&[])
+    );
+}
+
+#[test]
+fn empty_chunks_are_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        AsByteChunks([&[][..], &[]]),
+        AsByteChunks(std::iter::empty::<&[u8]>()),
+        AsBytes(&[])
+    );
+}
+
+#[test]
+fn reads_exactly_the_slice() {
+    // A buffer with a known pattern, only part of which is hashed
+    let mut buffer = [0xAAu8; 64];
+    buffer[16..20].copy_from_slice(b"abcd");
+    let window = &buffer[16..20];
+    equal!(
+        124936649695945685782166318067338664349, "860981d441e4538d726543a412e35594d6efd2994d729dae29973be183217484";
+        AsBytes(window),
+        AsBytes(b"abcd"),
+        AsBytes(&Vec::from(&b"abcd"[..]))
+    );
+
+    // The bytes around the slice do not contribute
+    let mut other = [0x55u8; 8];
+    other[2..6].copy_from_slice(b"abcd");
+    assert_eq!(
+        common::fast_stable_hash(&AsBytes(window)),
+        common::fast_stable_hash(&AsBytes(&other[2..6]))
+    );
+    not_equal!(AsBytes(window), AsBytes(&buffer[15..20]));
+    not_equal!(AsBytes(window), AsBytes(&buffer[16..21]));
+}
+
+#[test]
+fn spare_capacity_is_not_read() {
+    let mut buffer = Vec::with_capacity(1024);
+    buffer.extend_from_slice(b"abcd");
+    assert!(buffer.capacity() > buffer.len());
+    assert_eq!(
+        common::fast_stable_hash(&AsBytes(&buffer)),
+        common::fast_stable_hash(&AsBytes(b"abcd"))
+    );
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "trace")]
+
+use stable_hash::utils::canonical_encoding;
+use std::collections::{BTreeMap, HashMap};
+use xxhash_rust::xxh3::xxh3_128;
+
+#[test]
+fn small_map_is_pinned() {
+    let map: HashMap<u32, u32> = [(1, 2)].into_iter().collect();
+    // The entry (1, 2), encoded on its own as a member
+    let member = concat!(
+        // (1, 2).0 = 1, at {member}.0
+        "0200000000000000",
+        "000000000000000000",
+        "020000000000000000",
+        "0100000000000000",
+        "01",
+        // (1, 2).1 = 2, at {member}.1
+        "0200000000000000",
+        "000000000000000000",
+        "020100000000000000",
+        "0100000000000000",
+        "02",
+    );
+    // The member, written at the result path {}
+    let expected = format!(
+        "{}{}{:016x}{}",
+        "0100000000000000",
+        "010000000000000000",
+        (member.len() as u64 / 2).swap_bytes(),
+        member
+    );
+    assert_eq!(expected, hex::encode(canonical_encoding(&map)));
+    // The result another implementation gets by applying xxh3 to the same bytes
+    let hash = 74142369297247051687366615326472844668;
+    assert_eq!(hash, xxh3_128(&hex::decode(&expected).unwrap()));
+    assert_eq!(hash, xxh3_128(&canonical_encoding(&map)));
+}
+
+#[test]
+fn independent_of_order_and_map_type() {
+    let map: HashMap<String, u32> = (0..20).map(|i| (i.to_string(), i)).collect();
+    let reversed: HashMap<String, u32> = (0..20).rev().map(|i| (i.to_string(), i)).collect();
+    let btree: BTreeMap<String, u32> = (0..20).map(|i| (i.to_string(), i)).collect();
+    assert_eq!(canonical_encoding(&map), canonical_encoding(&reversed));
+    assert_eq!(canonical_encoding(&map), canonical_encoding(&btree));
+}
+
+#[test]
+fn distinct_values_are_distinct() {
+    assert_ne!(
+        canonical_encoding(&(1u32, 2u32)),
+        canonical_encoding(&(2u32, 1u32))
+    );
+    assert_ne!(
+        canonical_encoding(&vec![1u32]),
+        canonical_encoding(&vec![1u32, 0])
+    );
+    // Defaults write nothing
+    assert_eq!(Vec::<u8>::new(), canonical_encoding(&0u32));
+}
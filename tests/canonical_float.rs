@@ -0,0 +1,67 @@
+mod common;
+
+use stable_hash::utils::CanonicalFloat;
+
+#[test]
+fn zeros_collide_and_are_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        CanonicalFloat(0.0),
+        CanonicalFloat(-0.0),
+        0u32
+    );
+}
+
+#[test]
+fn nans_collide() {
+    let payload_nan = f64::from_bits(f64::NAN.to_bits() | 0x1234);
+    equal!(
+        252066144861436832309522649806354350879, "a9bd4150d291e2c6872699cd8768996d386b40519c7ed5679f8b637eb04d4201";
+        CanonicalFloat(f64::NAN),
+        CanonicalFloat(-f64::NAN),
+        CanonicalFloat(payload_nan),
+        CanonicalFloat(f64::INFINITY - f64::INFINITY)
+    );
+}
+
+#[test]
+fn normal() {
+    equal!(
+        17314125987090880051368921802811963802, "1a3703c0900b6611f578fcfb94d02e86dd976ce19ee3fad3e65e10eb3a2f99da";
+        CanonicalFloat(1.5),
+        CanonicalFloat(1.5f32 as f64)
+    );
+    not_equal!(CanonicalFloat(1.5), CanonicalFloat(-1.5));
+    not_equal!(CanonicalFloat(1.5), CanonicalFloat(1.5 + f64::EPSILON));
+}
+
+#[test]
+fn subnormal() {
+    let smallest = f64::from_bits(1);
+    assert!(smallest.is_subnormal());
+    equal!(
+        12810756349684581475853333683096438453, "5360c70136457486ae64432be1c83ba95d1823e15f7426f5aed560735f147d5a";
+        CanonicalFloat(smallest)
+    );
+    not_equal!(CanonicalFloat(smallest), CanonicalFloat(-smallest));
+    not_equal!(CanonicalFloat(smallest), CanonicalFloat(0.0));
+    not_equal!(
+        CanonicalFloat(f64::MIN_POSITIVE / 2.0),
+        CanonicalFloat(smallest)
+    );
+}
+
+#[test]
+fn infinite() {
+    equal!(
+        208251109666088226742622308820045561536, "31a9faa69437f48afc83c72f54045ad855ac9090cc81685a9bdc05f8681d909f";
+        CanonicalFloat(f64::INFINITY),
+        CanonicalFloat(1.0 / 0.0)
+    );
+    not_equal!(
+        CanonicalFloat(f64::INFINITY),
+        CanonicalFloat(f64::NEG_INFINITY)
+    );
+    not_equal!(CanonicalFloat(f64::INFINITY), CanonicalFloat(f64::NAN));
+    not_equal!(CanonicalFloat(f64::INFINITY), CanonicalFloat(f64::MAX));
+}
@@ -0,0 +1,20 @@
+#![cfg(feature = "test-utils")]
+
+use rand::{thread_rng, Rng as _};
+use stable_hash::utils::collision_stats;
+
+#[test]
+fn random_u64s_rarely_collide() {
+    let report = collision_stats(|| thread_rng().gen::<u64>(), 100_000);
+    assert_eq!(100_000, report.samples);
+    // With a 128 bit output a collision among so few samples is astronomically
+    // unlikely, so this threshold leaves room only for repeats of the u64 itself.
+    assert!(report.rate() < 1e-4, "{:?}", report);
+}
+
+#[test]
+fn repeated_values_are_counted() {
+    let report = collision_stats(|| 7u32, 10);
+    assert_eq!(9, report.collisions);
+    assert_eq!(0.9, report.rate());
+}
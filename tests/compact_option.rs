@@ -0,0 +1,68 @@
+mod common;
+
+use stable_hash::prelude::*;
+use stable_hash::utils::CompactOption;
+
+/// Always writes a tag, so that even the default writes a field. This meets
+/// the precondition of CompactOption.
+struct Tagged(u32);
+
+impl StableHash for Tagged {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        self.0.stable_hash(field_address.child(0), state);
+        state.write(field_address, &[]);
+    }
+}
+
+#[test]
+fn distinguishes_none_default_and_value() {
+    not_equal!(
+        CompactOption(Some(Tagged(0))),
+        CompactOption::<Tagged>(None)
+    );
+    not_equal!(
+        CompactOption(Some(Tagged(1))),
+        CompactOption::<Tagged>(None)
+    );
+    not_equal!(
+        CompactOption(Some(Tagged(1))),
+        CompactOption(Some(Tagged(0)))
+    );
+}
+
+#[test]
+fn some_matches_inner() {
+    equal!(
+        81670451744863619390280340607646744137, "2cb77187f77b6b64988ab9ef8a34e5a781d99909c3f71c54d6ac93699e271201";
+        CompactOption(Some(Tagged(5))),
+        Tagged(5)
+    );
+}
+
+#[test]
+fn none_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        CompactOption::<Tagged>(None),
+        ()
+    );
+}
+
+#[test]
+fn nested_option_meets_precondition() {
+    not_equal!(
+        CompactOption(Some(Some(0u32))),
+        CompactOption::<Option<u32>>(None)
+    );
+    // Unlike Option, the Some flag is not written twice
+    not_equal!(CompactOption(Some(Some(0u32))), Some(Some(0u32)));
+}
+
+#[test]
+fn default_payload_breaks_precondition() {
+    // 0 writes nothing, so Some(0) cannot be told apart from None
+    assert_eq!(
+        common::fast_stable_hash(&CompactOption(Some(0u32))),
+        common::fast_stable_hash(&CompactOption::<u32>(None))
+    );
+}
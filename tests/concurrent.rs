@@ -0,0 +1,57 @@
+use stable_hash::fast::{FastAddress, FastStableHasher};
+use stable_hash::prelude::*;
+use stable_hash::utils::ConcurrentStableHasher;
+use std::thread;
+
+const THREADS: u64 = 8;
+const FIELDS_PER_THREAD: u64 = 100;
+
+fn field(index: u64) -> String {
+    format!("field {}", index)
+}
+
+#[test]
+fn threads_writing_disjoint_fields_match_single_thread() {
+    let mut concurrent = ConcurrentStableHasher::new();
+    thread::scope(|scope| {
+        let threads: Vec<_> = (0..THREADS)
+            .map(|thread| {
+                let mut local = concurrent.local();
+                scope.spawn(move || {
+                    for i in 0..FIELDS_PER_THREAD {
+                        let index = thread * FIELDS_PER_THREAD + i;
+                        field(index).stable_hash(FastAddress::root().child(index), &mut *local);
+                    }
+                    local
+                })
+            })
+            .collect();
+        for thread in threads {
+            concurrent.combine(thread.join().unwrap());
+        }
+    });
+
+    let mut single = FastStableHasher::new();
+    for index in 0..THREADS * FIELDS_PER_THREAD {
+        field(index).stable_hash(FastAddress::root().child(index), &mut single);
+    }
+
+    assert_eq!(concurrent.finish(), single.finish());
+}
+
+#[test]
+fn nothing_written_matches_empty() {
+    let mut concurrent = ConcurrentStableHasher::new();
+    let local = concurrent.local();
+    concurrent.combine(local);
+    assert_eq!(concurrent.finish(), FastStableHasher::new().finish());
+}
+
+#[test]
+#[should_panic(expected = "Every local hasher must be combined before finishing")]
+fn live_local_at_finish_panics() {
+    let mut concurrent = ConcurrentStableHasher::new();
+    let mut local = concurrent.local();
+    "abc".stable_hash(FastAddress::root(), &mut *local);
+    concurrent.finish();
+}
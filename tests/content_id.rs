@@ -0,0 +1,49 @@
+use stable_hash::{content_id, crypto_stable_hash, ContentId, ParseContentIdError};
+
+#[test]
+fn string_form_is_stable() {
+    let id = content_id(&"abc");
+    assert_eq!(id.0, crypto_stable_hash(&"abc"));
+    assert_eq!(
+        "7ygv3bdvi4a3ns5zcri7twgg4wesdgwyybajnot6j7roga3hdvmq",
+        id.to_string()
+    );
+}
+
+#[test]
+fn round_trip() {
+    for value in [0u32, 1, 2, 1000, u32::MAX] {
+        let id = content_id(&value);
+        let s = id.to_string();
+        assert_eq!(52, s.len());
+        assert_eq!(Ok(id), s.parse());
+    }
+    for id in [ContentId([0; 32]), ContentId([0xff; 32])] {
+        assert_eq!(Ok(id), id.to_string().parse());
+    }
+    assert_eq!("a".repeat(52), ContentId([0; 32]).to_string());
+}
+
+#[test]
+fn rejects_non_canonical() {
+    let s = content_id(&"abc").to_string();
+    // Wrong length
+    assert_eq!(Err(ParseContentIdError), s[1..].parse::<ContentId>());
+    assert_eq!(
+        Err(ParseContentIdError),
+        format!("{}a", s).parse::<ContentId>()
+    );
+    // Uppercase and padding are not part of the encoding
+    assert_eq!(
+        Err(ParseContentIdError),
+        s.to_uppercase().parse::<ContentId>()
+    );
+    assert_eq!(
+        Err(ParseContentIdError),
+        format!("{}=", &s[1..]).parse::<ContentId>()
+    );
+    // The unused bits of the last character must be 0
+    let zeros = "a".repeat(52);
+    let nonzero = format!("{}b", &zeros[1..]);
+    assert_eq!(Err(ParseContentIdError), nonzero.parse::<ContentId>());
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use std::ops::ControlFlow::{self, Break, Continue};
+
+#[test]
+fn continue_and_break_do_not_collide() {
+    not_equal!(
+        ControlFlow::<u32, u32>::Continue(0),
+        ControlFlow::<u32, u32>::Break(0)
+    );
+    not_equal!(ControlFlow::<u32, u32>::Continue(0), 0u32);
+}
+
+#[test]
+fn continue_is_stable() {
+    equal!(
+        248034093987151538932499766988965482653, "692fccc2b8340a2a76eff1e360025811a0db7ce5c59a78f799d0a52ce2feea2e";
+        ControlFlow::<u32, u32>::Continue(0),
+        ControlFlow::<String, ()>::Continue(()),
+        Result::<u32, u32>::Ok(0)
+    );
+    equal!(
+        75519865775010932100351859059316031773, "da631c85f6197fe9f10ed8c09254242e0089518449bd595556805ae656df17c2";
+        ControlFlow::<u32, u32>::Continue(5),
+        Result::<u32, u32>::Ok(5)
+    );
+}
+
+#[test]
+fn break_is_stable() {
+    equal!(
+        135263302447443856369810803691068577694, "044100289e98a89ed394a64fec6960dbab147ca5b6560883c9ce5d65cd69bf51";
+        ControlFlow::<u32, u32>::Break(0),
+        Result::<u32, u32>::Err(0)
+    );
+    equal!(
+        113131898922032025747848950356497796515, "a2a1c6c307e14383cc532c26697f268aabb54a019e034b1f401bcb4a47c13e9e";
+        Break::<_, ()>("done"),
+        Result::<(), _>::Err("done")
+    );
+    not_equal!(Break::<_, &str>("done"), Continue::<&str, _>("done"));
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use stable_hash::utils::AsBytes;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+#[test]
+fn cow_slice() {
+    let items = vec![1u32, 0u32, 3u32];
+    let borrowed: Cow<[u32]> = Cow::Borrowed(&items[..]);
+    let owned: Cow<[u32]> = Cow::Owned(items.clone());
+    equal!(
+        25938894537210183800486651376472882088, "02c8cbdf182734a0501ecae77d4d5eaf5098ebd9312ec3dc8a673b8cdf091d8c";
+        borrowed,
+        owned,
+        &items[..],
+        items
+    );
+}
+
+#[test]
+fn cow_bytes() {
+    let bytes = vec![1u8, 2u8, 3u8];
+    let borrowed: Cow<[u8]> = Cow::Borrowed(&bytes[..]);
+    let owned: Cow<[u8]> = Cow::Owned(bytes.clone());
+    // Like &[u8], the bytes are hashed as a sequence of numbers.
+    equal!(
+        193833936596083547035584674840075484437, "f06836982af48669f211bf9c60bd27f8326540d528bd8ea9fda5f25d8b283a54";
+        borrowed,
+        owned,
+        &bytes[..]
+    );
+    // To hash as a sequence of bytes instead, use AsBytes
+    equal!(
+        83401086686245253164565997440049036767, "05b14e0b298ad31da8d1bc27bf02429a23efd03bfcfb543a34e2281d28a88d1c";
+        AsBytes(&borrowed),
+        AsBytes(&owned),
+        AsBytes(&bytes[..])
+    );
+}
+
+#[test]
+fn cow_str() {
+    let borrowed: Cow<str> = Cow::Borrowed("abc");
+    let owned: Cow<str> = Cow::Owned("abc".to_owned());
+    equal!(
+        225126920276043178050448780660223346301, "fe0d5d84754701b6cbb91451f9d8c6e589219ad8c04096ba7e4fe2e303671d59";
+        borrowed,
+        owned,
+        "abc"
+    );
+}
+
+#[test]
+fn cow_hash_map() {
+    let map: HashMap<String, u32> = (0..10).map(|i| (i.to_string(), i)).collect();
+    // Same entries, inserted in another order
+    let reversed: HashMap<String, u32> = (0..10).rev().map(|i| (i.to_string(), i)).collect();
+    let borrowed: Cow<HashMap<String, u32>> = Cow::Borrowed(&map);
+    let owned: Cow<HashMap<String, u32>> = Cow::Owned(reversed.clone());
+    equal!(
+        59228098621128064378133541232682555824, "894e26ecd41a3681d49a4487c6813ad23cf93827174fa3a7968fc797a1b3e2ac";
+        borrowed,
+        owned,
+        &map,
+        reversed
+    );
+}
+
+#[test]
+fn cow_hash_set() {
+    let set: HashSet<u32> = (0..10).collect();
+    let reversed: HashSet<u32> = (0..10).rev().collect();
+    let borrowed: Cow<HashSet<u32>> = Cow::Borrowed(&set);
+    let owned: Cow<HashSet<u32>> = Cow::Owned(reversed.clone());
+    equal!(
+        102902209920363598747986654851435635714, "1bf7fd4d0fe35ab4fc872d5c4af744df7f5c369dfe7de2591159398f8a318bf4";
+        borrowed,
+        owned,
+        &set,
+        reversed
+    );
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use stable_hash::utils::{OrderedCollection, UnorderedCollection};
+use stable_hash::{impl_stable_hash_ordered, impl_stable_hash_unordered, StableHash};
+use std::collections::HashSet;
+
+/// A set kept sorted in a Vec
+struct SortedVecSet<T>(Vec<T>);
+
+impl<T: Ord> SortedVecSet<T> {
+    fn new(items: impl IntoIterator<Item = T>) -> Self {
+        let mut items: Vec<T> = items.into_iter().collect();
+        items.sort();
+        items.dedup();
+        Self(items)
+    }
+}
+
+impl<T: StableHash> UnorderedCollection for SortedVecSet<T> {
+    fn members(&self) -> impl Iterator<Item = impl StableHash + '_> + '_ {
+        self.0.iter()
+    }
+}
+
+impl_stable_hash_unordered!(SortedVecSet<T>);
+
+/// A ring buffer, whose logical order starts at head
+struct Ring<T> {
+    buffer: Vec<T>,
+    head: usize,
+}
+
+impl<T: StableHash> OrderedCollection for Ring<T> {
+    fn items(&self) -> impl Iterator<Item = impl StableHash + '_> + '_ {
+        let (front, back) = self.buffer.split_at(self.head);
+        back.iter().chain(front)
+    }
+}
+
+impl_stable_hash_ordered!(Ring<T>);
+
+#[test]
+fn unordered_is_order_independent() {
+    let set: HashSet<&str> = ["a", "b", "c"].into_iter().collect();
+    equal!(
+        67123755585842743557241812785275078909, "1bf897e3d2f775d751fc70bbd953ff4df584731c3e1a99e7980f17cef80ca0d9";
+        SortedVecSet::new(["a", "b", "c"]),
+        SortedVecSet::new(["c", "a", "b", "a"]),
+        set
+    );
+    not_equal!(
+        SortedVecSet::new(["a", "b"]),
+        SortedVecSet::new(["a", "b", "c"])
+    );
+}
+
+#[test]
+fn ordered_matches_vec() {
+    let ring = Ring {
+        buffer: vec![3u32, 4, 1, 2],
+        head: 2,
+    };
+    equal!(
+        285508414681256648369727882238244784376, "0b886042493a05e1f4a96b1b6b44e2ecc3f052a45bafce8ce7423341de8fd5a2";
+        ring,
+        vec![1u32, 2, 3, 4]
+    );
+}
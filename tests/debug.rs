@@ -0,0 +1,91 @@
+#![cfg(feature = "debug")]
+
+use stable_hash::debug::*;
+use std::collections::HashMap;
+
+#[test]
+fn entry_hashes_are_sorted_and_stable() {
+    let mut a = HashMap::new();
+    a.insert(1u32, "one");
+    a.insert(2u32, "two");
+    a.insert(3u32, "three");
+
+    let mut b = HashMap::with_capacity(100);
+    b.insert(3u32, "three");
+    b.insert(2u32, "two");
+    b.insert(1u32, "one");
+
+    let hashes = debug_entry_hashes(&a);
+    assert_eq!(3, hashes.len());
+    assert!(hashes.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(hashes, debug_entry_hashes(&b));
+}
+
+#[test]
+fn entry_pairs_show_changed_value() {
+    let a: HashMap<String, u32> = (0..20).map(|i| (format!("key {}", i), i)).collect();
+    let mut b = HashMap::with_capacity(100);
+    for i in (0..20).rev() {
+        b.insert(format!("key {}", i), i);
+    }
+
+    let pairs = debug_entry_pairs(&a);
+    assert_eq!(20, pairs.len());
+    assert!(pairs.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(pairs, debug_entry_pairs(&b));
+
+    b.insert("key 7".to_string(), 700);
+    let changed = debug_entry_pairs(&b);
+    let differing: Vec<_> = pairs
+        .iter()
+        .zip(&changed)
+        .filter(|(before, after)| before != after)
+        .collect();
+    assert_eq!(1, differing.len());
+    let (before, after) = differing[0];
+    assert_eq!(before.0, after.0);
+    assert_ne!(before.1, after.1);
+}
+
+#[test]
+fn recorded_members_do_not_depend_on_order() {
+    let build = |keys: Vec<u32>| -> HashMap<u32, Vec<u32>> {
+        let mut map = HashMap::with_capacity(keys.len() * 3);
+        for key in keys {
+            map.insert(key, vec![key; key as usize]);
+        }
+        map
+    };
+    let a = build((0..50).collect());
+    let b = build((0..50).rev().collect());
+
+    let (hash_a, records_a) = record_unordered_members(|| stable_hash::fast_stable_hash(&a));
+    let (hash_b, records_b) = record_unordered_members(|| stable_hash::fast_stable_hash(&b));
+
+    // Recording does not change the output
+    assert_eq!(hash_a, stable_hash::fast_stable_hash(&a));
+    assert_eq!(hash_a, hash_b);
+
+    assert_eq!(1, records_a.len());
+    assert_eq!(50, records_a[0].len());
+    assert!(records_a[0].windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(records_a, records_b);
+}
+
+#[test]
+fn records_nested_collections() {
+    let mut inner = HashMap::new();
+    inner.insert(1u32, 2u32);
+    let mut outer = HashMap::new();
+    outer.insert("a", inner.clone());
+    outer.insert("b", inner);
+
+    let ((), records) = record_unordered_members(|| {
+        stable_hash::crypto_stable_hash(&outer);
+    });
+    // The outer map, and the inner map once for each entry of the outer map
+    assert_eq!(3, records.len());
+
+    let ((), nothing) = record_unordered_members(|| {});
+    assert!(nothing.is_empty());
+}
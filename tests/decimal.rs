@@ -0,0 +1,34 @@
+#![cfg(feature = "rust_decimal")]
+
+mod common;
+
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+fn d(s: &str) -> Decimal {
+    Decimal::from_str(s).unwrap()
+}
+
+#[test]
+fn scale_is_normalized() {
+    equal!(135263302447443856369810803691068577694, "044100289e98a89ed394a64fec6960dbab147ca5b6560883c9ce5d65cd69bf51"; d("1"), d("1.0"), d("1.00"), Decimal::new(1000, 3), 1u32);
+}
+
+#[test]
+fn fraction() {
+    equal!(122895080254462508518848623548257892411, "33c0abb8a92b4100a0324bbc4f39c7aebbf18a3e08809d2c81ea6a1770d076f2"; d("-1.5"), d("-1.50"), Decimal::new(-150, 2));
+}
+
+#[test]
+fn zero_is_default() {
+    equal!(102568403942768160221811810082933398928, "3428a4134bfdac56aa04614504705b0ffd1d48f27777b109a793e5a641324212"; (5u32, d("0")), (5u32, d("0.000")), (5u32, d("-0")), (5u32, 0u32));
+}
+
+#[test]
+fn distinct_values_ne() {
+    not_equal!(d("1.5"), d("15"));
+    not_equal!(d("1.5"), d("0.15"));
+    not_equal!(d("1.5"), d("-1.5"));
+    not_equal!(d("0.1"), d("1"));
+    not_equal!(d("10"), d("1"));
+}
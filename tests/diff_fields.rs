@@ -0,0 +1,68 @@
+use stable_hash::utils::{diff_fields, FieldPath, PathItem::Child};
+use stable_hash::*;
+use std::collections::HashSet;
+
+struct Inner {
+    x: u32,
+    y: String,
+}
+
+impl_stable_hash!(Inner { x, y });
+
+struct Outer {
+    id: u64,
+    a: Inner,
+    b: Inner,
+    tags: HashSet<&'static str>,
+}
+
+impl_stable_hash!(Outer { id, a, b, tags });
+
+fn outer() -> Outer {
+    Outer {
+        id: 7,
+        a: Inner {
+            x: 1,
+            y: "one".to_string(),
+        },
+        b: Inner {
+            x: 2,
+            y: "two".to_string(),
+        },
+        tags: ["red", "blue"].into_iter().collect(),
+    }
+}
+
+#[test]
+fn identical_values_have_no_diff() {
+    assert_eq!(diff_fields(&outer(), &outer()), Vec::<FieldPath>::new());
+}
+
+#[test]
+fn reports_exactly_the_changed_nested_fields() {
+    let mut changed = outer();
+    changed.a.y = "uno".to_string();
+    // Changing to the default means the field is only written on one side
+    changed.b.x = 0;
+
+    let diff = diff_fields(&outer(), &changed);
+    assert_eq!(
+        diff,
+        vec![
+            FieldPath(vec![Child(1), Child(1)]),
+            FieldPath(vec![Child(2), Child(0)]),
+        ]
+    );
+    let display: Vec<_> = diff.iter().map(|path| path.to_string()).collect();
+    assert_eq!(display, vec!["$.1.1", "$.2.0"]);
+}
+
+#[test]
+fn unordered_members_are_reported_at_the_collection() {
+    let mut changed = outer();
+    changed.tags.insert("green");
+
+    let diff = diff_fields(&outer(), &changed);
+    let display: Vec<_> = diff.iter().map(|path| path.to_string()).collect();
+    assert_eq!(display, vec!["$.3.{}"]);
+}
@@ -0,0 +1,18 @@
+mod common;
+
+use stable_hash::utils::DispatchKey;
+
+#[test]
+fn same_name_collides() {
+    equal!(
+        225126920276043178050448780660223346301, "fe0d5d84754701b6cbb91451f9d8c6e589219ad8c04096ba7e4fe2e303671d59";
+        DispatchKey("abc"),
+        DispatchKey("abc"),
+        "abc"
+    );
+}
+
+#[test]
+fn different_names_do_not_collide() {
+    not_equal!(DispatchKey("count_bytes"), DispatchKey("count_chars"));
+}
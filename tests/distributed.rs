@@ -0,0 +1,57 @@
+use stable_hash::crypto::CryptoStableHasher;
+use stable_hash::fast::FastStableHasher;
+use stable_hash::{crypto_stable_hash, fast_stable_hash, FieldAddress, StableHash, StableHasher};
+use std::collections::HashMap;
+
+fn map() -> HashMap<String, Vec<u64>> {
+    (0..1000u64)
+        .map(|i| (format!("key {}", i), (0..i % 7).collect()))
+        .collect()
+}
+
+/// Splits the map into disjoint shards by key
+fn shards(map: &HashMap<String, Vec<u64>>, count: usize) -> Vec<HashMap<String, Vec<u64>>> {
+    let mut shards = vec![HashMap::new(); count];
+    for (i, (key, value)) in map.iter().enumerate() {
+        shards[i % count].insert(key.clone(), value.clone());
+    }
+    shards
+}
+
+/// What a worker sends to the coordinator
+fn hash_shard<H: StableHasher>(shard: &HashMap<String, Vec<u64>>) -> Vec<u8> {
+    let mut hasher = H::new();
+    shard.stable_hash(H::Addr::root(), &mut hasher);
+    hasher.to_bytes().as_ref().to_vec()
+}
+
+#[test]
+fn fast_shards() {
+    let map = map();
+    let messages: Vec<Vec<u8>> = shards(&map, 4)
+        .iter()
+        .map(hash_shard::<FastStableHasher>)
+        .collect();
+
+    let mut combined = FastStableHasher::new();
+    for message in messages {
+        let shard = FastStableHasher::from_bytes(message.try_into().unwrap());
+        combined.mixin(&shard);
+    }
+    assert_eq!(combined.finish(), fast_stable_hash(&map));
+}
+
+#[test]
+fn crypto_shards() {
+    let map = map();
+    let messages: Vec<Vec<u8>> = shards(&map, 4)
+        .iter()
+        .map(hash_shard::<CryptoStableHasher>)
+        .collect();
+
+    let mut combined = CryptoStableHasher::new();
+    for message in messages {
+        combined.mixin(&CryptoStableHasher::from_bytes(message));
+    }
+    assert_eq!(combined.finish(), crypto_stable_hash(&map));
+}
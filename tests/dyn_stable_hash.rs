@@ -0,0 +1,50 @@
+mod common;
+
+use stable_hash::utils::DynStableHash;
+use std::collections::HashMap;
+
+fn config(reversed: bool) -> HashMap<String, Box<dyn DynStableHash>> {
+    let mut entries: Vec<(String, Box<dyn DynStableHash>)> = vec![
+        ("name".to_string(), Box::new("server")),
+        ("port".to_string(), Box::new(8080u16)),
+        ("verbose".to_string(), Box::new(true)),
+        ("peers".to_string(), Box::new(vec!["a", "b"])),
+    ];
+    if reversed {
+        entries.reverse();
+    }
+    entries.into_iter().collect()
+}
+
+#[test]
+fn heterogeneous_map_is_deterministic() {
+    equal!(
+        126117106405951409208970375308247991382, "5792697f96ff67f96f5dd2720fd711bca57d97a438c1185096f73668364faf68";
+        config(false),
+        config(true)
+    );
+}
+
+#[test]
+fn boxed_values() {
+    let small: Box<dyn DynStableHash> = Box::new(5u8);
+    let large: Box<dyn DynStableHash> = Box::new(5u64);
+    let text: Box<dyn DynStableHash> = Box::new("5");
+    assert_eq!(
+        common::fast_stable_hash(&small),
+        common::fast_stable_hash(&large)
+    );
+    not_equal!(small, text);
+    // The value is hashed into its own hasher, so the box does not match it
+    not_equal!(large, 5u64);
+}
+
+#[test]
+fn default_is_skipped() {
+    let empty: Box<dyn DynStableHash> = Box::new(0u32);
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        empty,
+        ()
+    );
+}
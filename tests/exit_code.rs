@@ -0,0 +1,33 @@
+mod common;
+
+use stable_hash::utils::ExitCode;
+
+#[test]
+fn matches_i32() {
+    equal!(
+        217998006954740137880519185293230341169, "4e4a546ea097d350990f263ab06689e5556a87d75bd9bb5351c64954564759ee";
+        ExitCode(2),
+        2i32,
+        2u8
+    );
+    equal!(
+        180758524059554348236761602192907109688, "3b3857570435fc1783744f7ab985dacfbd24f36a4ef642e6861cf7b65c6305cb";
+        ExitCode(-1),
+        -1i32
+    );
+}
+
+#[test]
+fn success_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        ExitCode(0),
+        ()
+    );
+}
+
+#[test]
+fn codes_do_not_collide() {
+    not_equal!(ExitCode(1), ExitCode(-1));
+    not_equal!(ExitCode(1), ExitCode(256));
+}
@@ -0,0 +1,94 @@
+use stable_hash::fast::FastAddr64StableHasher;
+use stable_hash::prelude::*;
+use stable_hash::fast_stable_hash;
+use std::collections::{HashMap, HashSet};
+
+fn fast_addr64_stable_hash(value: &impl StableHash) -> u128 {
+    let mut hasher = FastAddr64StableHasher::new();
+    value.stable_hash(u64::root(), &mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn address_is_u64() {
+    let root: u64 = <FastAddr64StableHasher as StableHasher>::Addr::root();
+    assert_eq!(17, root);
+    assert_eq!(17 * 486_187_739 + 3, root.child(3));
+}
+
+#[test]
+fn hashes_are_stable() {
+    assert_eq!(
+        223503892869648962353584038769094446544,
+        fast_addr64_stable_hash(&vec!["abc", "def"])
+    );
+    assert_eq!(
+        323284370797330636883676086160173337373,
+        fast_addr64_stable_hash(&(1u32, -1i64, true))
+    );
+}
+
+#[test]
+fn differs_from_fast() {
+    // Shallow addresses fit in 64 bits, so they are the same for both
+    assert_eq!(
+        fast_addr64_stable_hash(&vec!["abc", "def"]),
+        fast_stable_hash(&vec!["abc", "def"])
+    );
+    let deep = vec![vec![vec!["abc"]]];
+    assert_ne!(fast_addr64_stable_hash(&deep), fast_stable_hash(&deep));
+}
+
+#[test]
+fn same_values_agree() {
+    assert_eq!(
+        fast_addr64_stable_hash(&vec![1u32, 2, 3]),
+        fast_addr64_stable_hash(&[1u8, 2, 3])
+    );
+    assert_eq!(
+        fast_addr64_stable_hash(&5u64),
+        fast_addr64_stable_hash(&5u8)
+    );
+    assert_ne!(
+        fast_addr64_stable_hash(&(1u32, 0u32)),
+        fast_addr64_stable_hash(&(0u32, 1u32))
+    );
+}
+
+#[test]
+fn unordered_is_independent_of_order() {
+    let mut a = HashMap::new();
+    let mut b = HashMap::with_capacity(100);
+    for i in 0..50u32 {
+        a.insert(i, i.to_string());
+        b.insert(49 - i, (49 - i).to_string());
+    }
+    assert_eq!(fast_addr64_stable_hash(&a), fast_addr64_stable_hash(&b));
+
+    let set: HashSet<u32> = (0..50).collect();
+    let keys: HashSet<u32> = a.keys().copied().collect();
+    assert_eq!(
+        fast_addr64_stable_hash(&set),
+        fast_addr64_stable_hash(&keys)
+    );
+}
+
+#[test]
+fn mixin_and_round_trip() {
+    let root = u64::root();
+    let mut whole = FastAddr64StableHasher::new();
+    "a".stable_hash(root.child(0), &mut whole);
+    "b".stable_hash(root.child(1), &mut whole);
+
+    let mut left = FastAddr64StableHasher::new();
+    "a".stable_hash(root.child(0), &mut left);
+    let mut right = FastAddr64StableHasher::new();
+    "b".stable_hash(root.child(1), &mut right);
+
+    let mut mixed = FastAddr64StableHasher::from_bytes(left.to_bytes());
+    mixed.mixin(&right);
+    assert_eq!(whole, mixed);
+
+    mixed.unmix(&right);
+    assert_eq!(left.finish(), mixed.finish());
+}
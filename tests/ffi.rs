@@ -0,0 +1,41 @@
+#![cfg(feature = "ffi")]
+
+use stable_hash::ffi::{stable_hash_crypto_bytes, stable_hash_fast_bytes};
+use stable_hash::utils::AsBytes;
+use stable_hash::{crypto_stable_hash, fast_stable_hash};
+use std::ptr;
+
+fn fast(bytes: &[u8]) -> u128 {
+    let mut out = [0u8; 16];
+    unsafe { stable_hash_fast_bytes(bytes.as_ptr(), bytes.len(), out.as_mut_ptr()) };
+    u128::from_le_bytes(out)
+}
+
+fn crypto(bytes: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    unsafe { stable_hash_crypto_bytes(bytes.as_ptr(), bytes.len(), out.as_mut_ptr()) };
+    out
+}
+
+#[test]
+fn matches_as_bytes() {
+    for bytes in [&b"abc"[..], &[0, 0, 0], &[255; 1000]] {
+        assert_eq!(fast(bytes), fast_stable_hash(&AsBytes(bytes)));
+        assert_eq!(crypto(bytes), crypto_stable_hash(&AsBytes(bytes)));
+    }
+}
+
+#[test]
+fn empty_buffer_may_be_null() {
+    let mut fast_out = [0u8; 16];
+    let mut crypto_out = [0u8; 32];
+    unsafe {
+        stable_hash_fast_bytes(ptr::null(), 0, fast_out.as_mut_ptr());
+        stable_hash_crypto_bytes(ptr::null(), 0, crypto_out.as_mut_ptr());
+    }
+    assert_eq!(
+        u128::from_le_bytes(fast_out),
+        fast_stable_hash(&AsBytes(&[]))
+    );
+    assert_eq!(crypto_out, crypto_stable_hash(&AsBytes(&[])));
+}
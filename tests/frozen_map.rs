@@ -0,0 +1,102 @@
+mod common;
+
+use common::fast_stable_hash;
+use stable_hash::fast::{FastAddress, FastStableHasher};
+use stable_hash::utils::FrozenMap;
+use stable_hash::{FieldAddress, StableHash, StableHasher};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static HASHED: AtomicUsize = AtomicUsize::new(0);
+
+/// A key which counts how many times it has been hashed
+#[derive(PartialEq, Eq, Hash)]
+struct Counted(u32);
+
+impl StableHash for Counted {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        HASHED.fetch_add(1, Ordering::SeqCst);
+        self.0.stable_hash(field_address, state)
+    }
+}
+
+fn map() -> HashMap<String, Vec<u32>> {
+    (0..50u32)
+        .map(|i| (i.to_string(), (0..i).collect()))
+        .collect()
+}
+
+#[test]
+fn matches_live_map() {
+    let frozen = FrozenMap::new(map());
+    assert_eq!(frozen.fast_stable_hash(), fast_stable_hash(&map()));
+    assert_eq!(fast_stable_hash(&frozen), fast_stable_hash(&map()));
+    assert_eq!(
+        common::crypto_stable_hash_str(&frozen),
+        common::crypto_stable_hash_str(&map())
+    );
+    assert_eq!(Ok(()), common::check_for_child_errors(&frozen));
+    assert_eq!(frozen.get("3"), Some(&vec![0, 1, 2]));
+    assert_eq!(frozen.into_inner(), map());
+}
+
+#[test]
+fn matches_live_map_nested() {
+    let frozen = FrozenMap::new(map());
+    assert_eq!(
+        fast_stable_hash(&(1u8, vec![&frozen])),
+        fast_stable_hash(&(1u8, vec![&map()]))
+    );
+    assert_eq!(
+        common::crypto_stable_hash_str(&(1u8, vec![&frozen])),
+        common::crypto_stable_hash_str(&(1u8, vec![&map()]))
+    );
+
+    let field_address = FastAddress::root().child(1).child(0);
+    let mut cached = FastStableHasher::new();
+    frozen.stable_hash_fast(field_address, &mut cached);
+    let mut live = FastStableHasher::new();
+    map().stable_hash(field_address, &mut live);
+    assert_eq!(cached, live);
+}
+
+#[test]
+fn default_members() {
+    let live: HashMap<u32, u32> = [(0, 0), (1, 0)].into_iter().collect();
+    let frozen = FrozenMap::new(live.clone());
+    assert_eq!(frozen.fast_stable_hash(), fast_stable_hash(&live));
+
+    let set: HashMap<u32, ()> = [(0, ()), (1, ())].into_iter().collect();
+    let frozen = FrozenMap::new(set.clone());
+    assert_eq!(frozen.fast_stable_hash(), fast_stable_hash(&set));
+}
+
+#[test]
+fn hashes_entries_once() {
+    let live: HashMap<Counted, u32> = (0..10).map(|i| (Counted(i), i)).collect();
+    HASHED.store(0, Ordering::SeqCst);
+    let expected = fast_stable_hash(&live);
+    assert_eq!(HASHED.load(Ordering::SeqCst), 10);
+
+    HASHED.store(0, Ordering::SeqCst);
+    let frozen = FrozenMap::new(live);
+    assert_eq!(HASHED.load(Ordering::SeqCst), 10);
+    for _ in 0..3 {
+        assert_eq!(frozen.fast_stable_hash(), expected);
+        let mut state = FastStableHasher::new();
+        frozen.stable_hash_fast(FastAddress::root().child(2), &mut state);
+    }
+    assert_eq!(HASHED.load(Ordering::SeqCst), 10);
+
+    // The StableHash impl uses the cache as well, including when nested
+    for _ in 0..3 {
+        assert_eq!(fast_stable_hash(&frozen), expected);
+        let mut state = FastStableHasher::new();
+        (1u8, vec![&frozen]).stable_hash(FastAddress::root(), &mut state);
+    }
+    assert_eq!(HASHED.load(Ordering::SeqCst), 10);
+
+    // Other backends rehash the map
+    common::crypto_stable_hash_str(&frozen);
+    assert_eq!(HASHED.load(Ordering::SeqCst), 20);
+}
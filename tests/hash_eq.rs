@@ -0,0 +1,53 @@
+mod common;
+
+use stable_hash::fast_stable_hash;
+use stable_hash::utils::HashEq;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+fn std_hash(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn big(seed: u32) -> HashMap<u32, Vec<String>> {
+    (0..100)
+        .map(|i| (i, (0..10).map(|j| format!("{} {}", seed, i * j)).collect()))
+        .collect()
+}
+
+#[test]
+fn equal_values_compare_equal() {
+    let a = HashEq::new(big(1));
+    let b = HashEq::new(big(1));
+    let c = HashEq::new(big(2));
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_eq!(a.fast_hash(), fast_stable_hash(&big(1)));
+    assert_eq!(*a, big(1));
+}
+
+#[test]
+fn std_hash_is_consistent_with_eq() {
+    let a = HashEq::new(big(1));
+    let b = HashEq::new(big(1));
+    assert_eq!(std_hash(&a), std_hash(&b));
+
+    let mut set = HashSet::new();
+    assert!(set.insert(a));
+    assert!(!set.insert(b));
+    assert!(set.insert(HashEq::new(big(2))));
+    assert!(set.contains(&HashEq::new(big(2))));
+    assert_eq!(2, set.len());
+}
+
+#[test]
+fn hashes_like_inner() {
+    equal!(
+        225126920276043178050448780660223346301, "fe0d5d84754701b6cbb91451f9d8c6e589219ad8c04096ba7e4fe2e303671d59";
+        HashEq::new("abc"),
+        "abc"
+    );
+}
@@ -0,0 +1,67 @@
+use stable_hash::crypto::CryptoStableHasher;
+use stable_hash::fast::FastStableHasher;
+use stable_hash::utils::stable_hash_filtered;
+use stable_hash::{crypto_stable_hash, fast_stable_hash, FieldAddress, StableHasher};
+use std::collections::HashMap;
+
+fn map() -> HashMap<u32, String> {
+    (0..100).map(|i| (i, format!("value {}", i))).collect()
+}
+
+fn is_even(key: &u32, _: &String) -> bool {
+    key % 2 == 0
+}
+
+#[test]
+fn matches_filtered_map() {
+    let map = map();
+    let filtered: HashMap<u32, String> = map
+        .iter()
+        .filter(|(key, value)| is_even(key, value))
+        .map(|(key, value)| (*key, value.clone()))
+        .collect();
+
+    let mut fast = FastStableHasher::new();
+    stable_hash_filtered(&map, is_even, FieldAddress::root(), &mut fast);
+    assert_eq!(fast.finish(), fast_stable_hash(&filtered));
+
+    let mut crypto = CryptoStableHasher::new();
+    stable_hash_filtered(&map, is_even, FieldAddress::root(), &mut crypto);
+    assert_eq!(crypto.finish(), crypto_stable_hash(&filtered));
+}
+
+#[test]
+fn filter_by_value() {
+    let map = map();
+    let pred = |_: &u32, value: &String| value.ends_with('7');
+    let filtered: HashMap<u32, String> = map
+        .iter()
+        .filter(|(key, value)| pred(key, value))
+        .map(|(key, value)| (*key, value.clone()))
+        .collect();
+    assert_eq!(filtered.len(), 10);
+
+    let mut fast = FastStableHasher::new();
+    stable_hash_filtered(&map, pred, FieldAddress::root(), &mut fast);
+    assert_eq!(fast.finish(), fast_stable_hash(&filtered));
+}
+
+#[test]
+fn set_like_map() {
+    let set: HashMap<u32, ()> = (0..10).map(|i| (i, ())).collect();
+    let filtered: HashMap<u32, ()> = (0..5).map(|i| (i, ())).collect();
+
+    let mut fast = FastStableHasher::new();
+    stable_hash_filtered(&set, |key, _| *key < 5, FieldAddress::root(), &mut fast);
+    assert_eq!(fast.finish(), fast_stable_hash(&filtered));
+}
+
+#[test]
+fn nothing_matches() {
+    let mut fast = FastStableHasher::new();
+    stable_hash_filtered(&map(), |_, _| false, FieldAddress::root(), &mut fast);
+    assert_eq!(
+        fast.finish(),
+        fast_stable_hash(&HashMap::<u32, String>::new())
+    );
+}
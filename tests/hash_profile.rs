@@ -0,0 +1,19 @@
+use stable_hash::*;
+
+#[test]
+fn fast_profile_routes_to_fast_backend() {
+    let value = vec![1u32, 2u32];
+    assert_eq!(
+        stable_hash(&value, HashProfile::Fast),
+        HashOutput::Fast(fast_stable_hash(&value))
+    );
+}
+
+#[test]
+fn crypto_profile_routes_to_crypto_backend() {
+    let value = vec![1u32, 2u32];
+    assert_eq!(
+        stable_hash(&value, HashProfile::Crypto),
+        HashOutput::Crypto(crypto_stable_hash(&value))
+    );
+}
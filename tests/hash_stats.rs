@@ -0,0 +1,96 @@
+use stable_hash::crypto::CryptoStableHasher;
+use stable_hash::fast::FastStableHasher;
+use stable_hash::prelude::*;
+use stable_hash::utils::{stable_hash_with_stats, AsByteChunks, HashStats};
+use stable_hash::{crypto_stable_hash, fast_stable_hash};
+use std::collections::{HashMap, HashSet};
+
+fn value() -> (u32, HashMap<u32, u32>) {
+    (300, [(1, 10), (2, 20), (3, 30)].into_iter().collect())
+}
+
+#[test]
+fn output_is_unchanged() {
+    let (fast, _) = stable_hash_with_stats::<_, FastStableHasher>(&value());
+    assert_eq!(fast, fast_stable_hash(&value()));
+    let (crypto, _) = stable_hash_with_stats::<_, CryptoStableHasher>(&value());
+    assert_eq!(crypto, crypto_stable_hash(&value()));
+}
+
+#[test]
+fn map_with_three_entries() {
+    let (_, stats) = stable_hash_with_stats::<_, FastStableHasher>(&value());
+    let member_len = FastStableHasher::new().to_bytes().len() as u64;
+    assert_eq!(
+        stats,
+        HashStats {
+            // 300, then the key and value of each entry and the entry itself
+            elements_hashed: 1 + 3 * 2 + 3,
+            bytes_written: 2 + 3 * 2 + 3 * member_len,
+            sub_hashers_allocated: 3,
+        }
+    );
+}
+
+#[test]
+fn chunks_count_once() {
+    let payload = [1u8; 10];
+    let (_, stats) =
+        stable_hash_with_stats::<_, FastStableHasher>(&AsByteChunks(payload.chunks(3)));
+    assert_eq!(
+        stats,
+        HashStats {
+            elements_hashed: 1,
+            bytes_written: 10,
+            sub_hashers_allocated: 0,
+        }
+    );
+}
+
+#[test]
+fn default_is_free() {
+    let (_, stats) = stable_hash_with_stats::<_, FastStableHasher>(&HashMap::<u32, u32>::new());
+    assert_eq!(stats, HashStats::default());
+}
+
+struct Named {
+    x: u32,
+    y: u32,
+}
+
+impl StableHash for Named {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        self.x.stable_hash(field_address.named_child("x"), state);
+        self.y.stable_hash(field_address.named_child("y"), state);
+    }
+}
+
+#[test]
+fn named_fields_output_is_unchanged() {
+    let value = Named { x: 1, y: 2 };
+    let (fast, stats) = stable_hash_with_stats::<_, FastStableHasher>(&value);
+    assert_eq!(fast, fast_stable_hash(&value));
+    assert_eq!(stats.elements_hashed, 2);
+    let (crypto, _) = stable_hash_with_stats::<_, CryptoStableHasher>(&value);
+    assert_eq!(crypto, crypto_stable_hash(&value));
+}
+
+#[test]
+fn nested_members_are_counted() {
+    let value: HashMap<u32, HashSet<u32>> =
+        [(1, [1, 2].into_iter().collect())].into_iter().collect();
+    let (fast, stats) = stable_hash_with_stats::<_, FastStableHasher>(&value);
+    assert_eq!(fast, fast_stable_hash(&value));
+    // The entry, and each member of the set within it
+    assert_eq!(stats.sub_hashers_allocated, 3);
+}
+
+#[test]
+fn crypto_counts_match_fast() {
+    let (_, fast) = stable_hash_with_stats::<_, FastStableHasher>(&value());
+    let (_, crypto) = stable_hash_with_stats::<_, CryptoStableHasher>(&value());
+    assert_eq!(crypto.elements_hashed, fast.elements_hashed);
+    assert_eq!(crypto.sub_hashers_allocated, fast.sub_hashers_allocated);
+    // Each member is written as its serialized state, which is never empty
+    assert!(crypto.bytes_written > 2 + 3 * 2 + 3);
+}
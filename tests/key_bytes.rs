@@ -0,0 +1,46 @@
+mod common;
+
+use stable_hash::utils::{AsBytes, PublicKeyBytes, SignatureBytes};
+
+fn signature() -> [u8; 64] {
+    let mut bytes = [0u8; 64];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = (i as u8).wrapping_mul(37).wrapping_add(11);
+    }
+    bytes
+}
+
+#[test]
+fn signature_is_single_write() {
+    let bytes = signature();
+    equal!(
+        309406629173219115883277404188533255630, "b78ea9cb581b9a67514e94153bd257f77257c6bea112521328b613afe9a9cf46";
+        SignatureBytes(bytes),
+        AsBytes(&bytes)
+    );
+}
+
+#[test]
+fn compressed_public_key() {
+    let mut bytes = [0u8; 33];
+    bytes[0] = 2;
+    bytes[1..].copy_from_slice(&signature()[..32]);
+    equal!(
+        218628382735263127055283128716808287612, "bf97e6eecb1593467846e18e0395191909f184e81d98887a4a88842a5b4666b0";
+        PublicKeyBytes(bytes),
+        AsBytes(&bytes)
+    );
+}
+
+#[test]
+fn differs_from_sequence_of_numbers() {
+    let bytes = signature();
+    not_equal!(SignatureBytes(bytes), bytes.to_vec());
+}
+
+#[test]
+fn zero_bytes_are_not_default() {
+    // Unlike numbers, trailing zero bytes are significant
+    not_equal!(SignatureBytes([0u8; 64]), SignatureBytes([0u8; 0]));
+    not_equal!(PublicKeyBytes([0u8; 33]), PublicKeyBytes([0u8; 32]));
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use stable_hash::crypto::CryptoStableHasher;
+use stable_hash::fast::FastStableHasher;
+use stable_hash::prelude::*;
+use stable_hash::utils::concat_hash;
+use std::collections::LinkedList;
+
+#[test]
+fn matches_vec() {
+    let list: LinkedList<u32> = vec![1, 2, 3].into_iter().collect();
+    equal!(
+        193833936596083547035584674840075484437, "f06836982af48669f211bf9c60bd27f8326540d528bd8ea9fda5f25d8b283a54";
+        list,
+        vec![1u32, 2, 3]
+    );
+}
+
+#[test]
+fn order_matters() {
+    let list: LinkedList<u32> = vec![1, 2, 3].into_iter().collect();
+    let reversed: LinkedList<u32> = list.iter().rev().copied().collect();
+    not_equal!(list, reversed);
+}
+
+fn concat_matches_append<H: StableHasher>(a: &LinkedList<u32>, b: &LinkedList<u32>)
+where
+    H::Out: Eq + std::fmt::Debug,
+{
+    let mut concat = H::new();
+    concat_hash(a, b, H::Addr::root(), &mut concat);
+
+    let mut appended = a.clone();
+    appended.append(&mut b.clone());
+    let mut expected = H::new();
+    appended.stable_hash(H::Addr::root(), &mut expected);
+
+    assert_eq!(concat.finish(), expected.finish());
+}
+
+#[test]
+fn concat_hash_matches_concatenated_list() {
+    let a: LinkedList<u32> = vec![1, 0, 3].into_iter().collect();
+    let b: LinkedList<u32> = vec![4, 5, 0].into_iter().collect();
+    let empty = LinkedList::new();
+
+    for (a, b) in [
+        (&a, &b),
+        (&b, &a),
+        (&a, &empty),
+        (&empty, &b),
+        (&empty, &empty),
+    ] {
+        concat_matches_append::<FastStableHasher>(a, b);
+        concat_matches_append::<CryptoStableHasher>(a, b);
+    }
+}
+
+#[test]
+fn hashes_do_not_combine() {
+    // The hash of a splice is not the combined hashes of its parts,
+    // since the items of the second list move to new indices.
+    let a: LinkedList<u32> = vec![1, 2].into_iter().collect();
+    let b: LinkedList<u32> = vec![3].into_iter().collect();
+
+    let mut mixed = FastStableHasher::new();
+    a.stable_hash(FieldAddress::root(), &mut mixed);
+    let mut other = FastStableHasher::new();
+    b.stable_hash(FieldAddress::root(), &mut other);
+    mixed.mixin(&other);
+
+    let mut concat = FastStableHasher::new();
+    concat_hash(&a, &b, FieldAddress::root(), &mut concat);
+    assert_ne!(mixed.finish(), concat.finish());
+}
@@ -0,0 +1,328 @@
+mod common;
+
+use stable_hash::prelude::*;
+use stable_hash::*;
+
+struct NonEmptyString(String);
+
+impl_stable_hash_transparent!(NonEmptyString);
+
+struct Wrapped<'a>(&'a str);
+
+impl_stable_hash_transparent!(Wrapped<'a>);
+
+struct Label(String);
+
+impl_stable_hash!(Label(transparent));
+
+struct Raw(Vec<u8>);
+
+impl_stable_hash!(Raw(transparent: utils::AsBytes));
+
+/// A newtype as derive_more would generate with #[derive(Deref, From)]
+struct Meters(u32);
+
+impl std::ops::Deref for Meters {
+    type Target = u32;
+    fn deref(&self) -> &u32 {
+        &self.0
+    }
+}
+
+impl From<u32> for Meters {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl_stable_hash_transparent!(Meters);
+
+struct Field {
+    value: String,
+}
+
+impl_stable_hash!(Field { value });
+
+#[test]
+fn transparent_newtype_matches_inner() {
+    equal!(
+        225126920276043178050448780660223346301, "fe0d5d84754701b6cbb91451f9d8c6e589219ad8c04096ba7e4fe2e303671d59";
+        NonEmptyString("abc".to_string()),
+        Wrapped("abc"),
+        Label("abc".to_string()),
+        "abc".to_string()
+    );
+}
+
+#[test]
+fn transparent_modifier_matches_output() {
+    assert_eq!(
+        fast_stable_hash(&Raw(vec![1, 2, 3])),
+        fast_stable_hash(&utils::AsBytes(&[1, 2, 3]))
+    );
+}
+
+#[test]
+fn derived_newtype_matches_inner() {
+    equal!(
+        142312350356870347133330573639367315867, "5c7ee97b123d8d723abb6e470ce17edbea2de55891b76927d1255c7881880615";
+        Meters::from(5),
+        Meters(5),
+        *Meters(5),
+        5u32
+    );
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        Meters(0),
+        0u32
+    );
+}
+
+#[test]
+fn transparent_newtype_differs_from_single_field_struct() {
+    not_equal!(
+        NonEmptyString("abc".to_string()),
+        Field {
+            value: "abc".to_string()
+        }
+    );
+}
+
+enum Unit {
+    A,
+    B,
+    C,
+}
+
+impl_stable_hash_enum!(Unit { A = 0, B = 1, C = 2 });
+
+enum Shape<'a> {
+    Empty,
+    Circle(u32),
+    Square(u32),
+    Rect { width: u32, height: u32 },
+    Label(&'a str, u8),
+}
+
+impl_stable_hash_enum!(Shape<'a> {
+    Empty = 0,
+    Circle(radius) = 1,
+    Rect { width, height } = 2,
+    Label(text, size) = 3,
+    // Added later, so the index does not follow the declaration order
+    Square(side) = 4,
+});
+
+/// The encoding impl_stable_hash_enum! is documented to produce
+struct Manual<'a>(&'a Shape<'a>);
+
+impl StableHash for Manual<'_> {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        let variant = match self.0 {
+            Shape::Empty => 0u64,
+            Shape::Circle(radius) => {
+                radius.stable_hash(field_address.child(0), state);
+                1
+            }
+            Shape::Rect { width, height } => {
+                (width, height).stable_hash(field_address.child(0), state);
+                2
+            }
+            Shape::Label(text, size) => {
+                (text, size).stable_hash(field_address.child(0), state);
+                3
+            }
+            Shape::Square(side) => {
+                side.stable_hash(field_address.child(0), state);
+                4
+            }
+        };
+        variant.stable_hash(field_address, state);
+    }
+}
+
+#[test]
+fn unit_variants_do_not_collide() {
+    not_equal!(Unit::A, Unit::B);
+    not_equal!(Unit::B, Unit::C);
+    not_equal!(Unit::A, Unit::C);
+}
+
+#[test]
+fn first_variant_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        Unit::A,
+        Shape::Empty,
+        0u32
+    );
+}
+
+#[test]
+fn default_payloads_do_not_collide() {
+    not_equal!(Shape::Circle(0), Shape::Square(0));
+    not_equal!(Shape::Circle(0), Shape::Empty);
+    not_equal!(
+        Shape::Rect {
+            width: 0,
+            height: 0
+        },
+        Shape::Label("", 0)
+    );
+}
+
+#[test]
+fn enum_matches_documented_encoding() {
+    let shapes = [
+        Shape::Empty,
+        Shape::Circle(5),
+        Shape::Square(5),
+        Shape::Rect {
+            width: 2,
+            height: 3,
+        },
+        Shape::Label("abc", 0),
+    ];
+    for shape in &shapes {
+        assert_eq!(fast_stable_hash(shape), fast_stable_hash(&Manual(shape)));
+        assert_eq!(
+            crypto_stable_hash(shape),
+            crypto_stable_hash(&Manual(shape))
+        );
+    }
+    equal!(
+        2325858565820711845745051148989346132, "1a2c0472499201bb20d59b96a7ea67d760c076749747a163301e2549aba5f27e";
+        Shape::Rect {
+            width: 2,
+            height: 3
+        }
+    );
+}
+
+/// Id was once a bare String, and is being migrated to an enum
+enum Id {
+    Numeric(u64),
+    Named(String),
+    Legacy(String),
+}
+
+impl_stable_hash_enum!(Id {
+    Numeric(value) = 1,
+    Named(name) = 2,
+    #[stable_hash(transparent)]
+    Legacy(value),
+});
+
+#[test]
+fn transparent_variant_matches_inner() {
+    equal!(
+        13249647343332818818373963366501785578, "8575d3ca4299bc596524864cb259716ac3b8209560ace719eb05d1387775a69f";
+        Id::Legacy("alice".to_string()),
+        "alice"
+    );
+    not_equal!(
+        Id::Legacy("alice".to_string()),
+        Id::Named("alice".to_string())
+    );
+    not_equal!(Id::Numeric(7), Id::Named("7".to_string()));
+}
+
+mod declared {
+    #[derive(Clone, Copy)]
+    #[repr(u8)]
+    pub enum Level {
+        Off = 0,
+        Low = 1,
+        High = 7,
+    }
+
+    stable_hash::impl_stable_hash_repr_enum!(Level);
+}
+
+mod reordered {
+    // The same enum as above, but with the variants in a different order
+    #[derive(Clone, Copy)]
+    #[repr(u8)]
+    pub enum Level {
+        High = 7,
+        Off = 0,
+        Low = 1,
+    }
+
+    stable_hash::impl_stable_hash_repr_enum!(Level);
+}
+
+#[derive(Clone, Copy)]
+#[repr(u16)]
+enum Wide {
+    Low = 1,
+    High = 7,
+}
+
+impl_stable_hash_repr_enum!(Wide: u16);
+
+#[test]
+fn repr_enum_hashes_discriminant() {
+    equal!(
+        182291145613125634681349305781500295661, "3eb9f7797ae02ba0550f94f84ece072954e1b7316c6bfeb968c1b6a5e0dcdd91";
+        declared::Level::High,
+        reordered::Level::High,
+        Wide::High,
+        7u8
+    );
+    assert_eq!(
+        fast_stable_hash(&declared::Level::Low),
+        fast_stable_hash(&reordered::Level::Low)
+    );
+    assert_eq!(fast_stable_hash(&Wide::Low), fast_stable_hash(&1u8));
+}
+
+#[test]
+fn repr_enum_zero_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        declared::Level::Off,
+        reordered::Level::Off
+    );
+    not_equal!(declared::Level::Low, declared::Level::High);
+}
+
+/// A Poll-like type, as a custom Try type might be
+enum Progress<T> {
+    Done(T),
+    Waiting,
+    Failed(String),
+}
+
+impl_stable_hash_try_enum!(Progress<T> {
+    Done(value) = 0,
+    Failed(reason) = 1,
+    Waiting = 2,
+});
+
+#[test]
+fn try_enum_variants_do_not_collide() {
+    not_equal!(Progress::Done(0u32), Progress::<u32>::Waiting);
+    not_equal!(Progress::Done(0u32), Progress::<u32>::Failed(String::new()));
+    not_equal!(
+        Progress::<u32>::Waiting,
+        Progress::<u32>::Failed(String::new())
+    );
+    // No variant is the default
+    not_equal!(Progress::Done(0u32), ());
+    not_equal!(Progress::<u32>::Waiting, ());
+}
+
+#[test]
+fn try_enum_matches_result() {
+    equal!(
+        248034093987151538932499766988965482653, "692fccc2b8340a2a76eff1e360025811a0db7ce5c59a78f799d0a52ce2feea2e";
+        Progress::Done(0u32),
+        Result::<u32, String>::Ok(0)
+    );
+    equal!(
+        135263302447443856369810803691068577694, "044100289e98a89ed394a64fec6960dbab147ca5b6560883c9ce5d65cd69bf51";
+        Progress::<u32>::Failed(String::new()),
+        Result::<u32, u32>::Err(0)
+    );
+}
@@ -0,0 +1,61 @@
+use stable_hash::crypto::CryptoStableHasher;
+use stable_hash::fast::FastStableHasher;
+use stable_hash::utils::{key_commitment, value_commitment};
+use stable_hash::{crypto_stable_hash, fast_stable_hash, StableHasher};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+fn fast_keys<V>(map: &HashMap<&str, V>) -> u128 {
+    key_commitment::<FastStableHasher, _, _, _>(map).finish()
+}
+
+fn fast_values<V: stable_hash::StableHash>(map: &HashMap<&str, V>) -> u128 {
+    value_commitment::<FastStableHasher, _, _, _>(map).finish()
+}
+
+#[test]
+fn same_keys_different_values() {
+    let a: HashMap<&str, u32> = [("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+    let b: HashMap<&str, u32> = [("a", 1), ("b", 20), ("c", 3)].into_iter().collect();
+
+    assert_eq!(fast_keys(&a), fast_keys(&b));
+    assert_eq!(
+        key_commitment::<CryptoStableHasher, _, _, _>(&a).finish(),
+        key_commitment::<CryptoStableHasher, _, _, _>(&b).finish()
+    );
+    assert_ne!(fast_values(&a), fast_values(&b));
+    assert_ne!(fast_stable_hash(&a), fast_stable_hash(&b));
+    assert_ne!(crypto_stable_hash(&a), crypto_stable_hash(&b));
+}
+
+#[test]
+fn matches_collections() {
+    let map: HashMap<&str, u32> = [("a", 1), ("b", 1), ("c", 3)].into_iter().collect();
+    let keys: HashSet<&str> = map.keys().copied().collect();
+    let values: BinaryHeap<u32> = map.values().copied().collect();
+    assert_eq!(fast_keys(&map), fast_stable_hash(&keys));
+    assert_eq!(fast_values(&map), fast_stable_hash(&values));
+    assert_eq!(
+        value_commitment::<CryptoStableHasher, _, _, _>(&map).finish(),
+        crypto_stable_hash(&values)
+    );
+}
+
+#[test]
+fn commitments_do_not_bind_values_to_keys() {
+    let a: HashMap<&str, u32> = [("a", 1), ("b", 2)].into_iter().collect();
+    let swapped: HashMap<&str, u32> = [("a", 2), ("b", 1)].into_iter().collect();
+    assert_eq!(fast_keys(&a), fast_keys(&swapped));
+    assert_eq!(fast_values(&a), fast_values(&swapped));
+    assert_ne!(fast_stable_hash(&a), fast_stable_hash(&swapped));
+}
+
+#[test]
+fn disjoint_commitments_combine() {
+    let a: HashMap<&str, u32> = [("a", 1), ("b", 2)].into_iter().collect();
+    let b: HashMap<&str, u32> = [("c", 3)].into_iter().collect();
+    let merged: HashMap<&str, u32> = a.clone().into_iter().chain(b.clone()).collect();
+
+    let mut combined = key_commitment::<FastStableHasher, _, _, _>(&a);
+    combined.mixin(&key_commitment(&b));
+    assert_eq!(combined.finish(), fast_keys(&merged));
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "trace")]
+
+use stable_hash::utils::map_golden_json;
+use std::collections::HashMap;
+
+#[test]
+fn independent_of_insertion_order() {
+    let forward: HashMap<String, u32> = (0..50).map(|i| (format!("key {}", i), i)).collect();
+    let mut reverse = HashMap::with_capacity(500);
+    for i in (0..50).rev() {
+        reverse.insert(format!("key {}", i), i);
+    }
+    assert_eq!(map_golden_json(&forward), map_golden_json(&reverse));
+}
+
+#[test]
+fn golden() {
+    let map: HashMap<&str, u32> = [("b", 2), ("a", 1)].into_iter().collect();
+    let expected = concat!(
+        "{\n",
+        "  \"hash\": \"638938e5e6c5b08aa123fec8a69a3c43264235ec2d5dacddc6c24e40600a82b3\",\n",
+        "  \"entries\": [\n",
+        "    {\"key\": \"47368f93e61f2c9cf8498c9cfc8b35436af0d88cd02c7773ff75c7d07c07723e\", ",
+        "\"value\": \"044100289e98a89ed394a64fec6960dbab147ca5b6560883c9ce5d65cd69bf51\"},\n",
+        "    {\"key\": \"86652f70e1dc316144b1f904c08169666f88da8ebb4643732291e10f457a04cc\", ",
+        "\"value\": \"4e4a546ea097d350990f263ab06689e5556a87d75bd9bb5351c64954564759ee\"}\n",
+        "  ]\n",
+        "}\n",
+    );
+    assert_eq!(map_golden_json(&map), expected);
+}
+
+#[test]
+fn empty() {
+    assert_eq!(
+        map_golden_json(&HashMap::<u32, u32>::new()),
+        "{\n  \"hash\": \"48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b\",\n  \"entries\": []\n}\n"
+    );
+}
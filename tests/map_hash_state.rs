@@ -0,0 +1,192 @@
+use rand::{thread_rng, Rng};
+use stable_hash::fast_stable_hash;
+use stable_hash::utils::{
+    difference_hash, merge_disjoint_hash, symmetric_difference_hash, BTreeMapHashState,
+    MapHashState,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[test]
+fn matches_full_rehash() {
+    let mut map: HashMap<String, u64> = (0..10).map(|i| (format!("key {}", i), i)).collect();
+    let mut state = MapHashState::from_entries(&map);
+    assert_eq!(state.finish(), fast_stable_hash(&map));
+
+    let key = "key 3".to_string();
+    state.update_entry(&key, &3, &30);
+    map.insert(key, 30);
+    assert_eq!(state.finish(), fast_stable_hash(&map));
+}
+
+#[test]
+fn empty_matches_empty_map() {
+    assert_eq!(
+        MapHashState::<u32, u32>::new().finish(),
+        fast_stable_hash(&HashMap::<u32, u32>::new())
+    );
+}
+
+#[test]
+fn unit_values_match_set() {
+    let map: BTreeMap<u32, ()> = (0..5).map(|i| (i, ())).collect();
+    let mut state = MapHashState::from_entries(&map);
+    assert_eq!(state.finish(), fast_stable_hash(&map));
+    state.remove(&2, &());
+    let set: std::collections::HashSet<u32> = [0, 1, 3, 4].into_iter().collect();
+    assert_eq!(state.finish(), fast_stable_hash(&set));
+}
+
+#[test]
+fn fuzz_incremental_matches_full_rehash() {
+    let mut rng = thread_rng();
+    let mut map: HashMap<u16, u32> = HashMap::new();
+    let mut state = MapHashState::new();
+
+    for _ in 0..2000 {
+        // Few keys and values, so that updates, removes, and defaults are common
+        let key: u16 = rng.gen_range(0..32);
+        let value: u32 = rng.gen_range(0..4);
+        match (rng.gen_range(0..3), map.get(&key).copied()) {
+            (0, Some(old)) => {
+                state.remove(&key, &old);
+                map.remove(&key);
+            }
+            (_, Some(old)) => {
+                state.update_entry(&key, &old, &value);
+                map.insert(key, value);
+            }
+            (_, None) => {
+                state.insert(&key, &value);
+                map.insert(key, value);
+            }
+        }
+        assert_eq!(state.finish(), fast_stable_hash(&map));
+    }
+}
+
+#[test]
+fn fuzz_btree_map_incremental_matches_full_rehash() {
+    let mut rng = thread_rng();
+    let mut map: BTreeMap<String, u8> = BTreeMap::new();
+    let mut state = BTreeMapHashState::new();
+
+    for _ in 0..2000 {
+        let key = format!("key {}", rng.gen_range(0..40));
+        match map.remove(&key) {
+            Some(old) => state.remove_entry(&key, &old),
+            None => {
+                let value = rng.gen_range(0..3);
+                state.add_entry(&key, &value);
+                map.insert(key, value);
+            }
+        }
+        assert_eq!(state.finish(), fast_stable_hash(&map));
+    }
+    assert_eq!(BTreeMapHashState::from_map(&map).finish(), state.finish());
+}
+
+#[test]
+fn difference_matches_difference_map() {
+    let remote: HashMap<String, u64> = (0..20).map(|i| (format!("key {}", i), i)).collect();
+    let local: HashMap<String, u64> = remote
+        .iter()
+        .filter(|(_, value)| *value % 3 == 0)
+        .map(|(key, value)| (key.clone(), *value))
+        .collect();
+    let missing: HashMap<String, u64> = remote
+        .iter()
+        .filter(|(key, _)| !local.contains_key(*key))
+        .map(|(key, value)| (key.clone(), *value))
+        .collect();
+
+    let difference = difference_hash(
+        &MapHashState::from_entries(&remote),
+        &MapHashState::from_entries(&local),
+    );
+    assert_eq!(difference.finish(), fast_stable_hash(&missing));
+
+    // Equal maps have no difference
+    let same = difference_hash(
+        &MapHashState::from_entries(&remote),
+        &MapHashState::from_entries(&remote),
+    );
+    assert_eq!(
+        same.finish(),
+        fast_stable_hash(&HashMap::<String, u64>::new())
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "subset has more entries than superset")]
+fn difference_of_larger_map_panics() {
+    let small: HashMap<u32, u32> = (0..2).map(|i| (i, i)).collect();
+    let large: HashMap<u32, u32> = (0..5).map(|i| (i, i)).collect();
+    difference_hash(
+        &MapHashState::from_entries(&small),
+        &MapHashState::from_entries(&large),
+    );
+}
+
+#[test]
+fn symmetric_difference_matches_difference_map() {
+    let a: HashMap<u32, &str> = [(1, "one"), (2, "two"), (3, "three")].into_iter().collect();
+    let b: HashMap<u32, &str> = [(2, "two"), (3, "three"), (4, "four")]
+        .into_iter()
+        .collect();
+    let expected: HashMap<u32, &str> = [(1, "one"), (4, "four")].into_iter().collect();
+
+    assert_eq!(
+        symmetric_difference_hash(&a, &b).finish(),
+        fast_stable_hash(&expected)
+    );
+    assert_eq!(
+        symmetric_difference_hash(&b, &a).finish(),
+        fast_stable_hash(&expected)
+    );
+    assert_eq!(
+        symmetric_difference_hash(&a, &a).finish(),
+        fast_stable_hash(&HashMap::<u32, &str>::new())
+    );
+}
+
+#[test]
+fn symmetric_difference_of_changed_value() {
+    let a: HashMap<u32, &str> = [(1, "one"), (2, "two")].into_iter().collect();
+    let b: HashMap<u32, &str> = [(1, "uno"), (2, "two")].into_iter().collect();
+    // Both the old and new entries of the changed key are included
+    let expected: HashSet<(u32, &str)> = [(1, "one"), (1, "uno")].into_iter().collect();
+    assert_eq!(
+        symmetric_difference_hash(&a, &b).finish(),
+        fast_stable_hash(&expected)
+    );
+}
+
+#[test]
+fn merge_disjoint_matches_merged_map() {
+    let a: HashMap<String, u64> = (0..10).map(|i| (format!("key {}", i), i)).collect();
+    let b: HashMap<String, u64> = (10..25).map(|i| (format!("key {}", i), i)).collect();
+    let merged: HashMap<String, u64> = a.clone().into_iter().chain(b.clone()).collect();
+
+    assert_eq!(
+        merge_disjoint_hash(&a, &b).finish(),
+        fast_stable_hash(&merged)
+    );
+    assert_eq!(
+        merge_disjoint_hash(&b, &a).finish(),
+        fast_stable_hash(&merged)
+    );
+    assert_eq!(
+        merge_disjoint_hash(&a, &HashMap::new()).finish(),
+        fast_stable_hash(&a)
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "maps are not disjoint")]
+fn merge_overlapping_panics() {
+    let a: HashMap<u32, u32> = (0..5).map(|i| (i, i)).collect();
+    let b: HashMap<u32, u32> = (4..8).map(|i| (i, i * 2)).collect();
+    merge_disjoint_hash(&a, &b);
+}
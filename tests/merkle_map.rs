@@ -0,0 +1,54 @@
+use stable_hash::utils::{merkle_map_proof, merkle_map_root, verify_map_proof};
+use std::collections::BTreeMap;
+
+fn map(len: u32) -> BTreeMap<u32, String> {
+    (0..len).map(|i| (i * 3, format!("value {}", i))).collect()
+}
+
+#[test]
+fn valid_inclusion_proofs() {
+    // Includes sizes which are not powers of 2, where a node is unpaired
+    for len in [1, 2, 3, 5, 8, 13] {
+        let map = map(len);
+        let root = merkle_map_root(&map);
+        for (key, value) in &map {
+            let proof = merkle_map_proof(&map, key).unwrap();
+            assert!(verify_map_proof(&root, key, value, &proof));
+            // The proof is only for this entry
+            assert!(!verify_map_proof(&root, key, &"other".to_string(), &proof));
+            assert!(!verify_map_proof(&root, &(key + 1), value, &proof));
+        }
+    }
+}
+
+#[test]
+fn non_membership() {
+    let map = map(10);
+    let root = merkle_map_root(&map);
+    assert_eq!(merkle_map_proof(&map, &1), None);
+
+    // The proof of another entry does not show that an absent entry is included
+    let proof = merkle_map_proof(&map, &3).unwrap();
+    assert!(!verify_map_proof(&root, &1, &"value 1".to_string(), &proof));
+}
+
+#[test]
+fn root_changes_with_any_value() {
+    let map = map(9);
+    let root = merkle_map_root(&map);
+    for key in map.keys() {
+        let mut changed = map.clone();
+        changed.insert(*key, "changed".to_string());
+        assert_ne!(merkle_map_root(&changed), root);
+    }
+    assert_eq!(merkle_map_root(&map), root);
+}
+
+#[test]
+fn pinned() {
+    assert_eq!(merkle_map_root(&BTreeMap::<u32, u32>::new()), [0; 32]);
+    assert_eq!(
+        hex::encode(merkle_map_root(&map(3))),
+        "460958e0c6cc41cd73b9ac11c6af8c89342eb881f973d2e7b9d71aede917ccb4"
+    );
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use stable_hash::*;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[test]
+fn pointers_are_transparent() {
+    equal!(
+        142312350356870347133330573639367315867, "5c7ee97b123d8d723abb6e470ce17edbea2de55891b76927d1255c7881880615";
+        Box::new(5u32),
+        Rc::new(5u32),
+        Arc::new(5u32),
+        5u32
+    );
+}
+
+#[test]
+fn pin_is_transparent() {
+    equal!(
+        142312350356870347133330573639367315867, "5c7ee97b123d8d723abb6e470ce17edbea2de55891b76927d1255c7881880615";
+        Box::pin(5u32),
+        Rc::pin(5u32),
+        Pin::new(&5u32),
+        5u32
+    );
+    not_equal!(Some(Box::pin(0u32)), Option::<Pin<Box<u32>>>::None);
+}
+
+#[test]
+fn option_box() {
+    equal!(
+        6734611700770144518005769877837635513, "1de4f17c6a283e541c819b4d230e86c11d9a5162423c1cdb91ca5e4ebb481f30";
+        Some(Box::new(0u32)),
+        Some(0u32)
+    );
+    not_equal!(Some(Box::new(0u32)), Option::<Box<u32>>::None);
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        Option::<Box<u32>>::None,
+        Box::new(0u32)
+    );
+}
+
+#[test]
+fn box_option() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        Box::new(Option::<u32>::None),
+        Option::<u32>::None
+    );
+    equal!(
+        6734611700770144518005769877837635513, "1de4f17c6a283e541c819b4d230e86c11d9a5162423c1cdb91ca5e4ebb481f30";
+        Box::new(Some(0u32)),
+        Some(Box::new(0u32))
+    );
+    not_equal!(Box::new(Some(0u32)), Box::new(Option::<u32>::None));
+}
+
+#[test]
+fn vec_option() {
+    equal!(
+        239986022928596382492127625658548666472, "0ccd4eeb93a44227b61dfac7f3383b3cf80bac06e91fc97d076dd7b17468451a";
+        vec![None, Some(0u32)],
+        vec![None, Some(Box::new(0u32))]
+    );
+    not_equal!(vec![None, Some(0u32)], vec![Option::<u32>::None, None]);
+    // The length distinguishes trailing Nones
+    not_equal!(vec![Option::<u32>::None], Vec::<Option<u32>>::new());
+}
+
+#[test]
+fn option_vec() {
+    equal!(
+        6734611700770144518005769877837635513, "1de4f17c6a283e541c819b4d230e86c11d9a5162423c1cdb91ca5e4ebb481f30";
+        Some(Vec::<u32>::new()),
+        Some(Box::new(Vec::<u32>::new()))
+    );
+    not_equal!(Some(Vec::<u32>::new()), Option::<Vec<u32>>::None);
+    not_equal!(Some(vec![0u32]), Some(Vec::<u32>::new()));
+}
+
+struct Node {
+    value: u32,
+    next: Option<Box<Node>>,
+}
+
+impl_stable_hash!(Node { value, next });
+
+fn list(values: &[u32]) -> Option<Box<Node>> {
+    values
+        .iter()
+        .rev()
+        .fold(None, |next, &value| Some(Box::new(Node { value, next })))
+}
+
+#[test]
+fn recursive_list() {
+    equal!(
+        52127344545295470930390418907322757155, "a674881863ad1e80c91f83c330d7938528548d847061250a60a915ecd9cea40f";
+        list(&[1, 2, 3]),
+        Some((1u32, Some((2u32, Some((3u32, Option::<u32>::None))))))
+    );
+    // A trailing default node is still present
+    not_equal!(list(&[1, 2, 0]), list(&[1, 2]));
+}
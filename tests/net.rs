@@ -0,0 +1,40 @@
+mod common;
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+#[test]
+fn socket_addr_v4() {
+    let addr = SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080);
+    equal!(53514518566243901003808911126901553655, "eb4c1fa26cf8d274a079990467005ae4e5e84ba02775e3121fbc9295ea8a4d2f"; addr, SocketAddr::V4(addr));
+}
+
+#[test]
+fn socket_addr_v6() {
+    let addr = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 3);
+    equal!(250108167685149481373671251108313779656, "71d493f49fc5de13909256212647cc736a93e180f2efd76fe1d2a0983deef41f"; addr, SocketAddr::V6(addr));
+}
+
+#[test]
+fn socket_addr_v6_scope_id_is_used() {
+    not_equal!(
+        SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 1),
+        SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 2)
+    );
+}
+
+#[test]
+fn socket_addr_v6_flowinfo_is_ignored() {
+    equal!(
+        179675617148031141349911606143618815665, "61fdf54487f9abf85d41d0c846c2d53b58dd89bd2dbe2c98d695f85939fc5595";
+        SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 0),
+        SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 7, 0)
+    );
+}
+
+#[test]
+fn ip_addr() {
+    let v4 = Ipv4Addr::new(10, 0, 0, 1);
+    equal!(226143400304887395264804523929665413165, "9ef1fde99a6a3900cf2bf8f8ee01e438b34505bdb87728ea27a758e2a54d0061"; v4, IpAddr::V4(v4));
+    not_equal!(Ipv4Addr::UNSPECIFIED, Ipv6Addr::UNSPECIFIED);
+    not_equal!(v4, v4.to_ipv6_mapped());
+}
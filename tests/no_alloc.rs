@@ -0,0 +1,58 @@
+use stable_hash::fast_stable_hash;
+use stable_hash::utils::MapHashState;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::HashMap;
+
+/// Counts the allocations made by the current thread while enabled
+struct CountingAllocator;
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.with(Cell::get) {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        }
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations(f: impl FnOnce()) -> usize {
+    ALLOCATIONS.with(|count| count.set(0));
+    COUNTING.with(|counting| counting.set(true));
+    f();
+    COUNTING.with(|counting| counting.set(false));
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn update_entry_does_not_allocate() {
+    let mut map: HashMap<u64, String> = (0..100).map(|i| (i, format!("value {}", i))).collect();
+    let mut state = MapHashState::from_entries(&map);
+    let values: Vec<String> = (0..1000).map(|i| format!("updated {}", i)).collect();
+
+    let mut updates = 0;
+    for (i, new_value) in values.iter().enumerate() {
+        let key = (i % 100) as u64;
+        let old_value = &map[&key];
+        updates += allocations(|| state.update_entry(&key, old_value, new_value));
+        map.insert(key, new_value.clone());
+    }
+    assert_eq!(updates, 0);
+    assert_eq!(state.finish(), fast_stable_hash(&map));
+}
+
+#[test]
+fn counter_detects_allocation() {
+    assert_eq!(allocations(|| drop(vec![1u8])), 1);
+}
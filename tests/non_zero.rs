@@ -0,0 +1,55 @@
+mod common;
+
+use stable_hash::utils::CompactOption;
+use std::num::{NonZeroI64, NonZeroU32, NonZeroU8};
+
+fn nz(value: u32) -> NonZeroU32 {
+    NonZeroU32::new(value).unwrap()
+}
+
+#[test]
+fn matches_int() {
+    equal!(
+        135263302447443856369810803691068577694, "044100289e98a89ed394a64fec6960dbab147ca5b6560883c9ce5d65cd69bf51";
+        nz(1),
+        NonZeroU8::new(1).unwrap(),
+        1u32
+    );
+    equal!(
+        180758524059554348236761602192907109688, "3b3857570435fc1783744f7ab985dacfbd24f36a4ef642e6861cf7b65c6305cb";
+        NonZeroI64::new(-1).unwrap(),
+        -1i32
+    );
+}
+
+#[test]
+fn always_contributes() {
+    // The minimum value is not a default, and is not skipped
+    not_equal!(nz(1), ());
+    not_equal!(vec![nz(1)], vec![nz(2)]);
+}
+
+#[test]
+fn option() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        None::<NonZeroU32>,
+        ()
+    );
+    not_equal!(Some(nz(1)), None::<NonZeroU32>);
+    not_equal!(Some(nz(1)), nz(1));
+}
+
+#[test]
+fn compact_option_matches_int() {
+    for value in [0u32, 1, 2, 255, 256, u32::MAX] {
+        assert_eq!(
+            common::fast_stable_hash(&CompactOption(NonZeroU32::new(value))),
+            common::fast_stable_hash(&value)
+        );
+        assert_eq!(
+            common::crypto_stable_hash_str(&CompactOption(NonZeroU32::new(value))),
+            common::crypto_stable_hash_str(&value)
+        );
+    }
+}
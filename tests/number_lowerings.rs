@@ -1,5 +1,9 @@
 #[macro_use]
 mod common;
+use rand::{thread_rng, Rng as _};
+use stable_hash::prelude::*;
+use stable_hash::utils::AsInt;
+use stable_hash::{crypto_stable_hash, fast_stable_hash};
 use std::mem::size_of;
 
 macro_rules! nums_equal {
@@ -62,3 +66,55 @@ fn down_to_i16() {
 fn down_to_i8() {
     nums_equal!(-12i8, 67048966086700017767258589930187130954, "867b0b908a1ee3f4b1473febd9a76e8950692e631b1c4e39b4c18d26606cba40"; i8, i16, i32, i64, i128);
 }
+
+/// Hashes the int through AsInt with its full width little endian bytes,
+/// which is the general path that the int impls must agree with.
+struct General {
+    is_negative: bool,
+    little_endian: Vec<u8>,
+}
+
+impl StableHash for General {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        AsInt {
+            is_negative: self.is_negative,
+            little_endian: &self.little_endian,
+        }
+        .stable_hash(field_address, state)
+    }
+}
+
+macro_rules! matches_general {
+    ($($P:ty, $N:ty);+) => {
+        $(
+            let mut values: Vec<$P> = vec![0, 1, <$P>::MAX, <$P>::MAX - 1];
+            for shift in 0..<$P>::BITS {
+                let power = (1 as $P) << shift;
+                values.extend([power, power - 1, power.wrapping_add(1)]);
+            }
+            values.extend((0..100).map(|_| thread_rng().gen::<$P>()));
+
+            for value in values {
+                let general = General {
+                    is_negative: false,
+                    little_endian: value.to_le_bytes().to_vec(),
+                };
+                assert_eq!(fast_stable_hash(&value), fast_stable_hash(&general));
+                assert_eq!(crypto_stable_hash(&value), crypto_stable_hash(&general));
+
+                let signed = value as $N;
+                let general = General {
+                    is_negative: signed.is_negative(),
+                    little_endian: signed.unsigned_abs().to_le_bytes().to_vec(),
+                };
+                assert_eq!(fast_stable_hash(&signed), fast_stable_hash(&general));
+                assert_eq!(crypto_stable_hash(&signed), crypto_stable_hash(&general));
+            }
+        )+
+    };
+}
+
+#[test]
+fn ints_match_general_path() {
+    matches_general!(u8, i8; u16, i16; u32, i32; u64, i64; u128, i128; usize, isize);
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use std::cell::OnceCell;
+use std::sync::OnceLock;
+
+fn cell<T>(value: T) -> OnceCell<T> {
+    let cell = OnceCell::new();
+    assert!(cell.set(value).is_ok());
+    cell
+}
+
+fn lock<T>(value: T) -> OnceLock<T> {
+    let lock = OnceLock::new();
+    assert!(lock.set(value).is_ok());
+    lock
+}
+
+#[test]
+fn initialized_matches_value() {
+    equal!(
+        112158032357135841452069031126036725011, "a90da3ea018cb541982503d4523c7caa8577ebfd827b422322a70feedb4477ec";
+        cell("config".to_string()),
+        lock("config".to_string()),
+        "config"
+    );
+}
+
+#[test]
+fn uninitialized_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        OnceCell::<String>::new(),
+        OnceLock::<u32>::new(),
+        cell(0u32),
+        ()
+    );
+}
+
+#[test]
+fn hashing_does_not_initialize() {
+    let lock = OnceLock::<u32>::new();
+    let before = common::fast_stable_hash(&lock);
+    assert!(lock.get().is_none());
+    lock.get_or_init(|| 5);
+    assert_ne!(common::fast_stable_hash(&lock), before);
+    assert_eq!(
+        common::fast_stable_hash(&lock),
+        common::fast_stable_hash(&5u32)
+    );
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "ordered-float")]
+
+mod common;
+
+use ordered_float::OrderedFloat;
+use stable_hash::utils::CanonicalFloat;
+use std::collections::HashMap;
+
+#[test]
+fn matches_canonical_float() {
+    equal!(
+        252066144861436832309522649806354350879, "a9bd4150d291e2c6872699cd8768996d386b40519c7ed5679f8b637eb04d4201";
+        OrderedFloat(f64::NAN),
+        OrderedFloat(-f64::NAN),
+        OrderedFloat(f32::NAN),
+        CanonicalFloat(f64::NAN)
+    );
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        OrderedFloat(0.0f64),
+        OrderedFloat(-0.0f64),
+        OrderedFloat(-0.0f32),
+        CanonicalFloat(0.0)
+    );
+    assert_eq!(
+        common::fast_stable_hash(&OrderedFloat(1.5f32)),
+        common::fast_stable_hash(&OrderedFloat(1.5f64))
+    );
+}
+
+fn bins(zero: f64, nan: f64) -> HashMap<OrderedFloat<f64>, u32> {
+    [
+        (OrderedFloat(zero), 3),
+        (OrderedFloat(nan), 1),
+        (OrderedFloat(0.5), 7),
+        (OrderedFloat(f64::NEG_INFINITY), 2),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[test]
+fn map_with_edge_case_keys() {
+    let payload_nan = f64::from_bits(f64::NAN.to_bits() | 0x1234);
+    equal!(
+        327097819593490366368233846380868016189, "45793c84540dc94e98774e765ef11f33f7ccac75ebd7df0e51f33ea3bddb432c";
+        bins(0.0, f64::NAN),
+        bins(-0.0, f64::NAN),
+        bins(0.0, payload_nan),
+        bins(-0.0, -f64::NAN)
+    );
+    not_equal!(bins(0.0, f64::NAN), bins(f64::MIN_POSITIVE, f64::NAN));
+}
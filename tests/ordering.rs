@@ -0,0 +1,44 @@
+mod common;
+
+use std::cmp::Ordering::{self, Equal, Greater, Less};
+
+#[test]
+fn equal_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        Equal,
+        0u32,
+        false
+    );
+}
+
+#[test]
+fn less_and_greater_are_distinct() {
+    not_equal!(Less, Greater);
+    not_equal!(Less, Equal);
+    not_equal!(Greater, Equal);
+}
+
+#[test]
+fn comparison_results_are_stable() {
+    let results: Vec<Ordering> = [(1, 2), (2, 2), (3, 2)]
+        .iter()
+        .map(|(a, b)| i32::cmp(a, b))
+        .collect();
+    equal!(
+        221233062684046342331876669433921181031, "f8d3d7863386534214498a12c8d33de0a357d883ed2d227b8c6303faf3a38449";
+        results,
+        vec![Less, Equal, Greater]
+    );
+    not_equal!(vec![Less, Equal, Greater], vec![Greater, Equal, Less]);
+}
+
+#[test]
+fn all_equal_contributes_only_length() {
+    equal!(
+        11352587151805930934672686630111428748, "5e01a81eb252f2a2e7b8c628934a51d57b6ed47cf656d7f9cac597c98746ae64";
+        vec![Equal, Equal, Equal],
+        vec![0u32, 0, 0]
+    );
+    not_equal!(vec![Equal, Equal, Equal], vec![Equal, Equal]);
+}
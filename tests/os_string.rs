@@ -0,0 +1,49 @@
+mod common;
+
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+
+#[test]
+fn matches_str() {
+    equal!(
+        225126920276043178050448780660223346301, "fe0d5d84754701b6cbb91451f9d8c6e589219ad8c04096ba7e4fe2e303671d59";
+        OsString::from("abc"),
+        OsStr::new("abc"),
+        "abc"
+    );
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        OsString::new(),
+        ""
+    );
+}
+
+#[test]
+fn non_ascii_matches_str() {
+    // Encoded natively as UTF-16 on Windows, but hashed as UTF-8 everywhere
+    assert_eq!(
+        common::fast_stable_hash(&OsString::from("grüße/日本")),
+        common::fast_stable_hash(&"grüße/日本")
+    );
+}
+
+#[test]
+fn keyed_map_matches_string_keys() {
+    let keys = ["alpha", "beta", "gamma"];
+    let os: HashMap<OsString, u32> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| (OsString::from(key), i as u32))
+        .collect();
+    let strings: HashMap<String, u32> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| (key.to_string(), i as u32))
+        .collect();
+    // Pinned, so that the hash is the same on every platform
+    equal!(
+        222289344637196307169229994106969272066, "8f24991bde475d51cb8915187bc06d105838ce8ee453bf8dc5794f8d48b13a2e";
+        os,
+        strings
+    );
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use stable_hash::utils::PackedBools;
+
+#[test]
+fn pinned() {
+    equal!(
+        299464051683324816604625261040044136298, "83e1a4c18f8f3e865147968c3e2f16ef5dd84d6100a550517c8a57c0fe0e8007";
+        PackedBools(&[true, false, true, true, false, false, false, false, true])
+    );
+}
+
+#[test]
+fn lengths_do_not_collide() {
+    not_equal!(
+        PackedBools(&[true, false, true]),
+        PackedBools(&[true, false])
+    );
+    not_equal!(PackedBools(&[true, false]), PackedBools(&[true]));
+    not_equal!(PackedBools(&[true, false, true]), PackedBools(&[true]));
+    not_equal!(PackedBools(&[false]), PackedBools(&[]));
+}
+
+#[test]
+fn differs_from_element_wise() {
+    not_equal!(PackedBools(&[true, false, true]), [true, false, true]);
+    not_equal!(PackedBools(&[true, false, true]), vec![true, false, true]);
+}
+
+#[test]
+fn empty_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        PackedBools(&[]),
+        ()
+    );
+}
+
+#[cfg(feature = "bitvec")]
+#[test]
+fn matches_bitvec() {
+    use bitvec::prelude::BitVec;
+
+    let bools = [true, false, true, true, false, false, false, false, true];
+    let bits: BitVec = bools.iter().copied().collect();
+    assert_eq!(
+        common::fast_stable_hash(&PackedBools(&bools)),
+        common::fast_stable_hash(&bits)
+    );
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "parking_lot")]
+
+mod common;
+
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+
+#[test]
+fn mutex_is_transparent() {
+    equal!(
+        180758524059554348236761602192907109688, "3b3857570435fc1783744f7ab985dacfbd24f36a4ef642e6861cf7b65c6305cb";
+        Mutex::new(-1i32),
+        -1i32
+    );
+    let map: HashMap<&str, u32> = [("a", 1), ("b", 2)].into_iter().collect();
+    assert_eq!(
+        common::fast_stable_hash(&Mutex::new(map.clone())),
+        common::fast_stable_hash(&map)
+    );
+}
+
+#[test]
+fn rw_lock_is_transparent() {
+    equal!(
+        180758524059554348236761602192907109688, "3b3857570435fc1783744f7ab985dacfbd24f36a4ef642e6861cf7b65c6305cb";
+        RwLock::new(-1i32),
+        -1i32
+    );
+    let lock = RwLock::new(vec!["a", "b"]);
+    // Hashing only takes a read lock
+    let _reader = lock.read();
+    assert_eq!(
+        common::fast_stable_hash(&lock),
+        common::fast_stable_hash(&vec!["a", "b"])
+    );
+}
+
+#[test]
+fn default_is_skipped() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        Mutex::new(0u32),
+        RwLock::new(String::new()),
+        ()
+    );
+}
+
+#[test]
+fn not_poisoned_by_panic() {
+    let mutex = std::sync::Arc::new(Mutex::new(5u32));
+    let clone = mutex.clone();
+    let _ = std::thread::spawn(move || {
+        let mut guard = clone.lock();
+        *guard = 6;
+        panic!("while holding the lock");
+    })
+    .join();
+    assert_eq!(
+        common::fast_stable_hash(&*mutex),
+        common::fast_stable_hash(&6u32)
+    );
+}
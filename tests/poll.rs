@@ -0,0 +1,41 @@
+mod common;
+
+use stable_hash::impl_stable_hash_enum;
+use std::task::Poll;
+
+/// The encoding Poll is documented to match
+enum Manual {
+    Pending,
+    Ready(u32),
+}
+
+impl_stable_hash_enum!(Manual {
+    Pending = 0,
+    Ready(value) = 1,
+});
+
+#[test]
+fn pending_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        Poll::<u32>::Pending,
+        Manual::Pending,
+        ()
+    );
+}
+
+#[test]
+fn ready() {
+    equal!(
+        74714872239224304961055619010218516325, "3442424cbde417d6568d0110ceb3ef5f5f923d9f6f4f83af520a41fcc81f2d78";
+        Poll::Ready(5u32),
+        Manual::Ready(5)
+    );
+}
+
+#[test]
+fn ready_default_is_not_pending() {
+    not_equal!(Poll::Ready(0u32), Poll::<u32>::Pending);
+    not_equal!(Poll::Ready(0u32), Poll::Ready(1u32));
+    not_equal!(Poll::Ready(0u32), Some(0u32));
+}
@@ -1,10 +1,9 @@
-mod common;
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
+use std::hint::black_box;
 
-use common::*;
 use firestorm::profile_fn;
-use stable_hash::utils::AsBytes;
+use stable_hash::utils::{AsBytes, SignatureBytes};
 use stable_hash::*;
 
 #[test]
@@ -139,6 +138,8 @@ impl R for Value {
     }
 }
 
+// Only constructed by R::rand, which no benchmark currently calls
+#[allow(dead_code)]
 #[derive(Debug)]
 enum Value {
     Null,
@@ -177,6 +178,7 @@ impl StableHash for Value {
     }
 }
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct C {
     s: HashMap<String, Value>,
@@ -194,6 +196,7 @@ impl R for C {
     }
 }
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct A {
     v1: Vec<B>,
@@ -213,6 +216,7 @@ impl R for A {
     }
 }
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct B {
     a: u8,
@@ -229,3 +233,172 @@ impl R for B {
         }
     }
 }
+
+#[derive(Debug, Default)]
+struct Wide {
+    f0: bool,
+    f1: bool,
+    f2: u8,
+    f3: u8,
+    f4: u16,
+    f5: u16,
+    f6: u32,
+    f7: u32,
+    f8: u64,
+    f9: u64,
+    f10: bool,
+    f11: u8,
+    f12: u16,
+    f13: u32,
+    f14: u64,
+    f15: bool,
+}
+
+impl_stable_hash!(Wide {
+    f0,
+    f1,
+    f2,
+    f3,
+    f4,
+    f5,
+    f6,
+    f7,
+    f8,
+    f9,
+    f10,
+    f11,
+    f12,
+    f13,
+    f14,
+    f15
+});
+
+impl R for Wide {
+    fn rand() -> Self {
+        Self {
+            f0: R::rand(),
+            f1: R::rand(),
+            f2: R::rand(),
+            f3: R::rand(),
+            f4: thread_rng().gen(),
+            f5: thread_rng().gen(),
+            f6: thread_rng().gen(),
+            f7: thread_rng().gen(),
+            f8: thread_rng().gen(),
+            f9: thread_rng().gen(),
+            f10: R::rand(),
+            f11: R::rand(),
+            f12: thread_rng().gen(),
+            f13: thread_rng().gen(),
+            f14: thread_rng().gen(),
+            f15: R::rand(),
+        }
+    }
+}
+
+/// Many small fields, where the fixed cost of each write dominates.
+#[test]
+#[ignore = "benchmark"]
+fn wide_struct_small_fields() {
+    let data: Vec<Wide> = (0..10_000).map(|_| R::rand()).collect();
+
+    fn profile(data: &[Wide]) {
+        profile_fn!(profile);
+        for value in data {
+            black_box(fast_stable_hash(value));
+        }
+    }
+
+    if firestorm::enabled() {
+        firestorm::bench("./firestorm", || profile(&data)).unwrap();
+    }
+}
+
+/// Maps with a handful of entries, where the independent hasher
+/// created for each entry is a significant part of the cost.
+#[test]
+#[ignore = "benchmark"]
+fn small_maps() {
+    let maps: Vec<HashMap<u32, u64>> = [1u32, 4, 8]
+        .into_iter()
+        .flat_map(|size| {
+            (0..10_000).map(move |_| (0..size).map(|k| (k, thread_rng().gen())).collect())
+        })
+        .collect();
+
+    fn profile(maps: &[HashMap<u32, u64>]) {
+        profile_fn!(profile);
+        for map in maps {
+            black_box(fast_stable_hash(map));
+        }
+    }
+
+    if firestorm::enabled() {
+        firestorm::bench("./firestorm", || profile(&maps)).unwrap();
+    }
+}
+
+/// A 64 byte signature written once as bytes, compared to one write per byte.
+#[test]
+#[ignore = "benchmark"]
+fn signatures() {
+    let signatures: Vec<[u8; 64]> = (0..10_000)
+        .map(|_| {
+            let mut bytes = [0u8; 64];
+            thread_rng().fill_bytes(&mut bytes);
+            bytes
+        })
+        .collect();
+    let vecs: Vec<Vec<u8>> = signatures.iter().map(|s| s.to_vec()).collect();
+
+    fn signature_bytes(signatures: &[[u8; 64]]) {
+        profile_fn!(signature_bytes);
+        for signature in signatures {
+            black_box(fast_stable_hash(&SignatureBytes(*signature)));
+        }
+    }
+
+    fn vec_u8(vecs: &[Vec<u8>]) {
+        profile_fn!(vec_u8);
+        for signature in vecs {
+            black_box(fast_stable_hash(signature));
+        }
+    }
+
+    if firestorm::enabled() {
+        firestorm::bench("./firestorm", || {
+            signature_bytes(&signatures);
+            vec_u8(&vecs);
+        })
+        .unwrap();
+    }
+}
+
+/// A bare integer as a cache key, where the whole cost is a single write.
+#[test]
+#[ignore = "benchmark"]
+fn single_primitive() {
+    let data: Vec<u64> = (0..1_000_000).map(|_| thread_rng().gen()).collect();
+
+    fn random(data: &[u64]) {
+        profile_fn!(random);
+        for value in data {
+            black_box(fast_stable_hash(value));
+        }
+    }
+
+    fn constant(count: usize) {
+        profile_fn!(constant);
+        for _ in 0..count {
+            black_box(fast_stable_hash(black_box(&5u64)));
+        }
+    }
+
+    if firestorm::enabled() {
+        firestorm::bench("./firestorm", || {
+            random(&data);
+            constant(data.len());
+        })
+        .unwrap();
+    }
+}
@@ -0,0 +1,48 @@
+use stable_hash::crypto::CryptoStableHasher;
+use stable_hash::crypto_stable_hash;
+use stable_hash::prelude::*;
+use stable_hash::utils::range_commitment;
+use std::collections::BTreeMap;
+
+fn map() -> BTreeMap<u32, String> {
+    (0..30).map(|i| (i, format!("value {}", i))).collect()
+}
+
+#[test]
+fn adjacent_ranges_combine_to_whole_map() {
+    let map = map();
+    let low = range_commitment(&map, ..10);
+    let middle = range_commitment(&map, 10..20);
+    let high = range_commitment(&map, 20..);
+
+    let mut combined = low.clone();
+    combined.mixin(&middle);
+    combined.mixin(&high);
+    assert_eq!(combined.finish(), crypto_stable_hash(&map));
+
+    // The order of combination does not matter
+    let mut reversed = high;
+    reversed.mixin(&middle);
+    reversed.mixin(&low);
+    assert_eq!(reversed, combined);
+
+    assert_eq!(
+        range_commitment(&map, ..).finish(),
+        crypto_stable_hash(&map)
+    );
+}
+
+#[test]
+fn range_matches_sub_map() {
+    let map = map();
+    let sub_map: BTreeMap<_, _> = map.range(5..=12).map(|(k, v)| (*k, v.clone())).collect();
+    assert_eq!(
+        range_commitment(&map, 5..=12).finish(),
+        crypto_stable_hash(&sub_map)
+    );
+}
+
+#[test]
+fn empty_range_is_empty_commitment() {
+    assert_eq!(range_commitment(&map(), 100..), CryptoStableHasher::new());
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use std::ops::Bound;
+
+#[test]
+fn range() {
+    equal!(
+        101547953410834148751777044975774502089, "7c3c4b1e7ee3d4af7b3b06782f216ad917281549275f14405d47ce869470ed06";
+        5u32..10u32,
+        5u64..10u64,
+        (Bound::Included(5u32), Bound::Excluded(10u32))
+    );
+}
+
+#[test]
+fn range_full_is_default() {
+    equal!(
+        102568403942768160221811810082933398928, "3428a4134bfdac56aa04614504705b0ffd1d48f27777b109a793e5a641324212";
+        (5u32, ..),
+        (5u32, 0u32)
+    );
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn range_kinds_ne() {
+    not_equal!(5u32.., ..5u32);
+    not_equal!(5u32.., 5u32..5u32);
+    not_equal!(5u32.., ..);
+    not_equal!(..5u32, 5u32..5u32);
+    not_equal!(..5u32, ..);
+    not_equal!(5u32..5u32, ..);
+    not_equal!(5u32..5u32, 5u32..=5u32);
+    not_equal!(..5u32, ..=5u32);
+    not_equal!(0u32..5u32, ..5u32);
+    not_equal!(5u32..0u32, 5u32..);
+}
+
+#[test]
+fn range_ne_number() {
+    not_equal!(5u32.., 5u32);
+    not_equal!(..5u32, 5u32);
+    not_equal!(5u32..5u32, 5u32);
+    not_equal!(0u32..0u32, 0u32);
+    not_equal!(-1i32.., -1i32);
+}
@@ -0,0 +1,73 @@
+use stable_hash::utils::schema_and_data_hash;
+use stable_hash::*;
+use std::collections::HashSet;
+
+struct Record {
+    id: u64,
+    name: String,
+    tags: HashSet<&'static str>,
+}
+
+impl_stable_hash!(Record { id, name, tags });
+
+struct Renamed {
+    id: u64,
+    label: String,
+    extra: u32,
+}
+
+impl_stable_hash!(Renamed { id, label, extra });
+
+#[test]
+fn same_schema_different_data() {
+    let a = Record {
+        id: 1,
+        name: "a".to_string(),
+        tags: ["x"].into_iter().collect(),
+    };
+    let b = Record {
+        id: 2,
+        name: "bbb".to_string(),
+        tags: ["y", "z"].into_iter().collect(),
+    };
+    let (schema_a, data_a) = schema_and_data_hash(&a);
+    let (schema_b, data_b) = schema_and_data_hash(&b);
+    assert_eq!(schema_a, schema_b);
+    assert_ne!(data_a, data_b);
+    assert_eq!(data_a, fast_stable_hash(&a));
+}
+
+#[test]
+fn different_schema() {
+    let record = Record {
+        id: 1,
+        name: "a".to_string(),
+        tags: ["x"].into_iter().collect(),
+    };
+    let renamed = Renamed {
+        id: 1,
+        label: "a".to_string(),
+        extra: 5,
+    };
+    assert_ne!(
+        schema_and_data_hash(&record).0,
+        schema_and_data_hash(&renamed).0
+    );
+    // A sequence of a different length has a different schema
+    assert_ne!(
+        schema_and_data_hash(&vec![1u32, 2]).0,
+        schema_and_data_hash(&vec![1u32, 2, 3]).0
+    );
+    assert_eq!(
+        schema_and_data_hash(&vec![1u32, 2]).0,
+        schema_and_data_hash(&vec![3u32, 4]).0
+    );
+}
+
+#[test]
+fn defaults_are_not_part_of_the_schema() {
+    assert_ne!(
+        schema_and_data_hash(&(1u32, 0u32)).0,
+        schema_and_data_hash(&(1u32, 1u32)).0
+    );
+}
@@ -0,0 +1,24 @@
+use stable_hash::fast_stable_hash;
+
+// () writes nothing, so a sequence of units hashes as only its length.
+// A Vec of a zero-sized type does not allocate, which allows the length
+// alone to be checked without constructing any items.
+#[test]
+fn len_is_written_as_u64() {
+    for len in [1u64, 255, 256, 65536] {
+        assert_eq!(
+            fast_stable_hash(&vec![(); len as usize]),
+            fast_stable_hash(&len)
+        );
+    }
+}
+
+// Iterates more than u32::MAX items, which takes a while without optimizations.
+// Run with: cargo test --release --test sequence_len -- --ignored
+#[test]
+#[ignore = "slow"]
+fn huge_len_is_written_as_u64() {
+    let len = u32::MAX as u64 + 2;
+    let units = vec![(); len as usize];
+    assert_eq!(fast_stable_hash(&units), fast_stable_hash(&len));
+}
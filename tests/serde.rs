@@ -0,0 +1,253 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use serde::Serialize;
+use stable_hash::utils::SerdeStableHash;
+use stable_hash::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[derive(Serialize)]
+struct Inner {
+    flag: bool,
+    name: String,
+}
+
+impl_stable_hash!(Inner { flag, name });
+
+#[derive(Serialize)]
+enum Kind {
+    Empty,
+    Number(i64),
+    Pair(u8, u8),
+}
+
+impl StableHash for Kind {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        let variant = match self {
+            Self::Empty => 0u32,
+            Self::Number(n) => {
+                n.stable_hash(field_address.child(0), state);
+                1
+            }
+            Self::Pair(a, b) => {
+                (a, b).stable_hash(field_address.child(0), state);
+                2
+            }
+        };
+        variant.stable_hash(field_address, state);
+    }
+}
+
+#[derive(Serialize)]
+struct Outer {
+    id: u32,
+    tags: Vec<u16>,
+    counts: HashMap<String, u32>,
+    inner: Option<Inner>,
+    kinds: Vec<Kind>,
+}
+
+impl_stable_hash!(Outer {
+    id,
+    tags,
+    counts,
+    inner,
+    kinds
+});
+
+fn outer() -> Outer {
+    let mut counts = HashMap::new();
+    counts.insert("a".to_string(), 1);
+    counts.insert("b".to_string(), 0);
+    Outer {
+        id: 7,
+        tags: vec![1, 0, 3],
+        counts,
+        inner: Some(Inner {
+            flag: true,
+            name: "inner".to_string(),
+        }),
+        kinds: vec![Kind::Empty, Kind::Number(-5), Kind::Pair(0, 9)],
+    }
+}
+
+#[test]
+fn serde_matches_hand_written() {
+    let value = outer();
+    assert_eq!(
+        fast_stable_hash(&value),
+        fast_stable_hash(&SerdeStableHash::try_new(&value).unwrap())
+    );
+    assert_eq!(
+        crypto_stable_hash(&value),
+        crypto_stable_hash(&SerdeStableHash::try_new(&value).unwrap())
+    );
+    assert_eq!(
+        Ok(()),
+        common::check_for_child_errors(&SerdeStableHash::try_new(&value).unwrap())
+    );
+}
+
+#[test]
+fn serde_default_fields_are_skipped() {
+    #[derive(Serialize)]
+    struct One {
+        one: u32,
+    }
+    #[derive(Serialize)]
+    struct Two {
+        one: u32,
+        two: Option<String>,
+    }
+    equal!(
+        102568403942768160221811810082933398928, "3428a4134bfdac56aa04614504705b0ffd1d48f27777b109a793e5a641324212";
+        SerdeStableHash::try_new(One { one: 5 }).unwrap(),
+        SerdeStableHash::try_new(Two { one: 5, two: None }).unwrap()
+    );
+}
+
+#[derive(Serialize)]
+enum Shape {
+    Empty,
+    Circle(u32),
+    Rect { width: u32, height: u32 },
+    Label(String, u8),
+}
+
+impl_stable_hash_enum!(Shape {
+    Empty = 0,
+    Circle(radius) = 1,
+    Rect { width, height } = 2,
+    Label(text, size) = 3,
+});
+
+#[test]
+fn serde_matches_enum_macro() {
+    let shapes = vec![
+        Shape::Empty,
+        Shape::Circle(0),
+        Shape::Rect {
+            width: 2,
+            height: 0,
+        },
+        Shape::Label("abc".to_string(), 1),
+    ];
+    for shape in &shapes {
+        assert_eq!(
+            fast_stable_hash(shape),
+            fast_stable_hash(&SerdeStableHash::try_new(shape).unwrap())
+        );
+    }
+    assert_eq!(
+        crypto_stable_hash(&shapes),
+        crypto_stable_hash(&SerdeStableHash::try_new(&shapes).unwrap())
+    );
+}
+
+#[test]
+fn serde_unit_map_matches_set() {
+    let map: HashMap<u32, ()> = (0..5).map(|i| (i, ())).collect();
+    let set: HashSet<u32> = map.keys().copied().collect();
+    assert_eq!(
+        fast_stable_hash(&SerdeStableHash::try_new(&map).unwrap()),
+        fast_stable_hash(&map)
+    );
+    assert_eq!(
+        fast_stable_hash(&SerdeStableHash::try_new(&map).unwrap()),
+        fast_stable_hash(&set)
+    );
+    assert_eq!(
+        crypto_stable_hash(&SerdeStableHash::try_new(&map).unwrap()),
+        crypto_stable_hash(&set)
+    );
+}
+
+#[test]
+fn serde_split_entries_match_map() {
+    use serde::ser::{SerializeMap, Serializer};
+
+    /// Serializes its entries with separate serialize_key and serialize_value calls
+    struct Split<K, V>(Vec<(K, V)>);
+
+    impl<K: Serialize, V: Serialize> Serialize for Split<K, V> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(Some(self.0.len()))?;
+            for (key, value) in &self.0 {
+                map.serialize_key(key)?;
+                map.serialize_value(value)?;
+            }
+            map.end()
+        }
+    }
+
+    let units = Split((0..5).map(|i| (i, ())).collect());
+    let set: HashSet<u32> = (0..5).collect();
+    assert_eq!(
+        fast_stable_hash(&SerdeStableHash::try_new(&units).unwrap()),
+        fast_stable_hash(&set)
+    );
+
+    let values = Split((0..5).map(|i| (i, i * 2)).collect());
+    let map: HashMap<u32, u32> = values.0.iter().copied().collect();
+    assert_eq!(
+        fast_stable_hash(&SerdeStableHash::try_new(&values).unwrap()),
+        fast_stable_hash(&map)
+    );
+
+    // Keys which write at several addresses, including the members of a map
+    type Key = (i32, Vec<i32>, BTreeMap<i32, i32>);
+    let key = |i: i32| -> Key { (-i, vec![i, 0], (0..i).map(|j| (j, j + 1)).collect()) };
+    let entries: Vec<(Key, u32)> = (0..5).map(|i| (key(i), i as u32)).collect();
+    let map: HashMap<_, _> = entries.iter().cloned().collect();
+    assert_eq!(
+        fast_stable_hash(&SerdeStableHash::try_new(&Split(entries.clone())).unwrap()),
+        fast_stable_hash(&map)
+    );
+    assert_eq!(
+        crypto_stable_hash(&SerdeStableHash::try_new(&Split(entries.clone())).unwrap()),
+        crypto_stable_hash(&map)
+    );
+
+    let units: Vec<_> = entries.into_iter().map(|(key, _)| (key, ())).collect();
+    let set: HashSet<_> = units.iter().map(|(key, _)| key.clone()).collect();
+    assert_eq!(
+        fast_stable_hash(&SerdeStableHash::try_new(&Split(units.clone())).unwrap()),
+        fast_stable_hash(&set)
+    );
+    assert_eq!(
+        crypto_stable_hash(&SerdeStableHash::try_new(&Split(units)).unwrap()),
+        crypto_stable_hash(&set)
+    );
+}
+
+#[test]
+fn serde_rejects_unsupported_values() {
+    use serde::ser::{Error, Serializer};
+
+    #[derive(Serialize)]
+    struct Reading {
+        id: u32,
+        value: f64,
+    }
+
+    let err = SerdeStableHash::try_new(Reading { id: 1, value: 0.5 })
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "f64 is not supported");
+
+    let mut nested = HashMap::new();
+    nested.insert(1u32, vec![Some(1.5f32)]);
+    assert!(SerdeStableHash::try_new(&nested).is_err());
+
+    struct Failing;
+
+    impl Serialize for Failing {
+        fn serialize<S: Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            Err(S::Error::custom("cannot serialize"))
+        }
+    }
+
+    let err = SerdeStableHash::try_new((1u8, Failing)).err().unwrap();
+    assert_eq!(err.to_string(), "cannot serialize");
+}
@@ -0,0 +1,46 @@
+use stable_hash::utils::{commit_set, SetCommitment};
+use std::collections::HashSet;
+
+fn members() -> Vec<String> {
+    (0..100).map(|i| format!("peer {}", i)).collect()
+}
+
+#[test]
+fn members_test_positive() {
+    let set: HashSet<String> = members().into_iter().collect();
+    let commitment = commit_set(&set);
+    for member in &set {
+        assert!(commitment.might_contain(member));
+    }
+}
+
+#[test]
+fn independent_of_insertion_order() {
+    let forward: HashSet<String> = members().into_iter().collect();
+    let mut reverse = HashSet::with_capacity(1000);
+    for member in members().into_iter().rev() {
+        reverse.insert(member);
+    }
+    assert_eq!(commit_set(&forward), commit_set(&reverse));
+
+    let restored = SetCommitment::from_bytes(*commit_set(&forward).as_bytes());
+    assert_eq!(restored, commit_set(&reverse));
+}
+
+#[test]
+fn few_false_positives() {
+    let set: HashSet<String> = members().into_iter().collect();
+    let commitment = commit_set(&set);
+    let false_positives = (0..1000)
+        .map(|i| format!("stranger {}", i))
+        .filter(|value| commitment.might_contain(value))
+        .count();
+    assert!(false_positives < 20, "{}", false_positives);
+}
+
+#[test]
+fn empty_set_contains_nothing() {
+    let commitment = commit_set(&HashSet::<u32>::new());
+    assert!(!commitment.might_contain(&0u32));
+    assert!(commitment.as_bytes().iter().all(|byte| *byte == 0));
+}
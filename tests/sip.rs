@@ -0,0 +1,51 @@
+use stable_hash::sip::sip_stable_hash_keyed;
+use std::collections::HashSet;
+
+const KEY: [u8; 16] = *b"0123456789abcdef";
+const OTHER_KEY: [u8; 16] = *b"fedcba9876543210";
+
+fn sip(value: &impl stable_hash::StableHash) -> u64 {
+    sip_stable_hash_keyed(value, KEY)
+}
+
+#[test]
+fn stable_for_fixed_key() {
+    assert_eq!(sip(&"abc"), 8430584838198730408);
+    assert_eq!(sip(&(5u32, vec![1u8, 2])), 15398786169160568550);
+}
+
+#[test]
+fn differs_across_keys() {
+    for value in ["abc", "def"] {
+        assert_ne!(sip(&value), sip_stable_hash_keyed(&value, OTHER_KEY));
+    }
+    // Members of unordered collections are keyed as well
+    let set: HashSet<u32> = (0..10).collect();
+    let other: HashSet<u32> = (10..20).collect();
+    assert_ne!(sip(&set), sip_stable_hash_keyed(&set, OTHER_KEY));
+    assert_ne!(sip(&set), sip(&other));
+}
+
+#[test]
+fn backward_compatible() {
+    // Adding a default field
+    assert_eq!(sip(&(5u32,)), sip(&(5u32, 0u32)));
+    assert_eq!(sip(&(5u32,)), sip(&(5u32, Option::<String>::None)));
+    assert_ne!(sip(&(5u32,)), sip(&(5u32, 1u32)));
+    // Widening an int
+    assert_eq!(sip(&5u8), sip(&5u64));
+    assert_eq!(sip(&-5i32), sip(&-5i128));
+    // Defaults
+    assert_eq!(sip(&0u32), sip(&""));
+    assert_ne!(sip(&Some(0u32)), sip(&Option::<u32>::None));
+}
+
+#[test]
+fn unordered_is_independent_of_order() {
+    let a: HashSet<String> = (0..100).map(|i| i.to_string()).collect();
+    let mut b = HashSet::with_capacity(1000);
+    for i in (0..100).rev() {
+        b.insert(i.to_string());
+    }
+    assert_eq!(sip(&a), sip(&b));
+}
@@ -0,0 +1,49 @@
+use stable_hash::crypto_stable_hash;
+use stable_hash::utils::sorted_by_hash_digest;
+use std::collections::HashMap;
+
+fn map() -> HashMap<String, Vec<u32>> {
+    (0..40)
+        .map(|i| (format!("key {}", i), vec![i; i as usize % 3]))
+        .collect()
+}
+
+#[test]
+fn independent_of_insertion_order() {
+    let mut entries: Vec<_> = map().into_iter().collect();
+    entries.sort();
+    let mut reverse = HashMap::with_capacity(400);
+    for (key, value) in entries.into_iter().rev() {
+        reverse.insert(key, value);
+    }
+    assert_eq!(
+        sorted_by_hash_digest(&map()),
+        sorted_by_hash_digest(&reverse)
+    );
+}
+
+#[test]
+fn pinned_map() {
+    assert_eq!(
+        hex::encode(sorted_by_hash_digest(&map())),
+        "8a5d0bba74add20857cdd267ee9a43a8f6d102a445ae5a1add349367ef7ec7ad"
+    );
+    assert_ne!(sorted_by_hash_digest(&map()), crypto_stable_hash(&map()));
+}
+
+#[test]
+fn pinned_empty() {
+    assert_eq!(
+        hex::encode(sorted_by_hash_digest(&HashMap::<u32, u32>::new())),
+        "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b"
+    );
+}
+
+#[test]
+fn pinned() {
+    let map: HashMap<&str, u32> = [("a", 1), ("b", 2)].into_iter().collect();
+    assert_eq!(
+        hex::encode(sorted_by_hash_digest(&map)),
+        "801ae190b2eefa168d4763b1ef373a2c11122fdadf40c1a27953a389a2a2d5af"
+    );
+}
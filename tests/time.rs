@@ -0,0 +1,82 @@
+mod common;
+
+use std::time::{Duration, UNIX_EPOCH};
+
+#[test]
+fn epoch_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        UNIX_EPOCH,
+        UNIX_EPOCH + Duration::from_secs(5) - Duration::from_secs(5),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn after_epoch() {
+    equal!(
+        55305771269467211633535349354288904465, "d5ec3852ebb1f6ce7e6814ad44734808702fa2d40896d90681f071f1910cf935";
+        UNIX_EPOCH + Duration::from_millis(5_250),
+        (Duration::new(5, 250_000_000), false)
+    );
+}
+
+#[test]
+fn before_epoch() {
+    equal!(
+        210841376456777713395999120677695362232, "0f2707eed7a23ff5d82d5ba9358b05cafc0795768765d84f8513bed19e3d8fc6";
+        UNIX_EPOCH - Duration::from_millis(5_250),
+        (Duration::new(5, 250_000_000), true)
+    );
+}
+
+#[test]
+fn sign_is_explicit() {
+    not_equal!(
+        UNIX_EPOCH + Duration::from_secs(5),
+        UNIX_EPOCH - Duration::from_secs(5)
+    );
+    not_equal!(
+        UNIX_EPOCH - Duration::from_nanos(1),
+        UNIX_EPOCH + Duration::from_nanos(1)
+    );
+}
+
+#[test]
+fn duration_fields() {
+    equal!(
+        58392854798493933918539677906030164735, "8f57057229a0a2398167e8c88098757aba7e134ecd01708b713d9fa83956ff50";
+        Duration::new(5, 7),
+        (5u64, 7u32)
+    );
+    not_equal!(Duration::from_secs(1), Duration::from_nanos(1));
+}
+
+/// Rounds to the nearest nanosecond, as a value with finer precision would be
+/// when received as a Duration.
+fn from_picos(picos: u64) -> Duration {
+    Duration::from_nanos((picos + 500) / 1000)
+}
+
+#[test]
+fn sub_nanosecond_precision_is_lost() {
+    equal!(
+        131388640610182721509770059875567307264, "e42078f0d81976bed8b8987b18a588fa7f065f10ea6caacab1774c2053e1be20";
+        from_picos(1_000_000_001_400),
+        from_picos(1_000_000_000_600),
+        Duration::new(1, 1)
+    );
+    not_equal!(from_picos(1_000_000_001_400), from_picos(1_000_000_001_600));
+}
+
+#[test]
+fn max_does_not_overflow() {
+    equal!(
+        43221672617837856468393511776759551977, "a47d984be10b2146dec3b408338b7d00c8e0070f634724eeb6a321dce5c5b81a";
+        Duration::MAX,
+        Duration::new(u64::MAX, 999_999_999),
+        (u64::MAX, 999_999_999u32)
+    );
+    not_equal!(Duration::MAX, Duration::MAX - Duration::from_nanos(1));
+    not_equal!(Duration::MAX, Duration::from_secs(u64::MAX));
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "time")]
+
+mod common;
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use time::{Date, Month, OffsetDateTime, Time, UtcOffset};
+
+fn instant(nanos: i128) -> OffsetDateTime {
+    OffsetDateTime::from_unix_timestamp_nanos(nanos).unwrap()
+}
+
+#[test]
+fn offset_is_normalized() {
+    let utc = instant(1_700_000_000_123_456_789);
+    let plus_two = utc.to_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+    let minus_five = utc.to_offset(UtcOffset::from_hms(-5, -30, 0).unwrap());
+    assert_ne!(utc.hour(), plus_two.hour());
+    equal!(
+        203177474699022175709943411453357064311, "7bd15ab21bb8e79a17b4ffd81bef52cbaebbf9fd32a7b983731d0e6add940009";
+        utc,
+        plus_two,
+        minus_five,
+        UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789)
+    );
+}
+
+#[test]
+fn matches_system_time() {
+    for nanos in [
+        0i128,
+        1,
+        -1,
+        1_500_000_000,
+        -1_500_000_000,
+        -86_400_000_000_000,
+    ] {
+        let magnitude = Duration::from_nanos(nanos.unsigned_abs() as u64);
+        let system_time = if nanos < 0 {
+            UNIX_EPOCH - magnitude
+        } else {
+            UNIX_EPOCH + magnitude
+        };
+        assert_eq!(
+            common::fast_stable_hash(&instant(nanos)),
+            common::fast_stable_hash(&system_time)
+        );
+        assert_eq!(
+            common::crypto_stable_hash_str(&instant(nanos)),
+            common::crypto_stable_hash_str(&system_time)
+        );
+    }
+    not_equal!(instant(1_000), instant(-1_000));
+}
+
+#[test]
+fn epoch_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        OffsetDateTime::UNIX_EPOCH,
+        SystemTime::UNIX_EPOCH,
+        Time::MIDNIGHT
+    );
+}
+
+#[test]
+fn date_and_time() {
+    equal!(
+        140716050595564239314991361751848673709, "96b31cb964535fc1b4ed5ad70e12978155be8473897134b036f4ba70763de874";
+        Date::from_calendar_date(2024, Month::March, 5).unwrap(),
+        (2024i32, 3u8, 5u8)
+    );
+    equal!(
+        124339481631994977010282187456775562143, "dae1f0d85cc0a29f1f7d074a01e14d40a819374a16d24c96d6f16b35fd52efa4";
+        Time::from_hms_nano(13, 0, 7, 42).unwrap(),
+        (13u8, 0u8, 7u8, 42u32)
+    );
+}
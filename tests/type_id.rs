@@ -0,0 +1,132 @@
+mod common;
+
+use stable_hash::prelude::*;
+
+struct Plain {
+    one: u32,
+}
+
+impl StableHash for Plain {
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        self.one.stable_hash(field_address.child(0), state);
+    }
+}
+
+#[derive(Clone)]
+struct Meters {
+    value: u32,
+}
+
+impl StableHash for Meters {
+    const TYPE_ID: Option<u64> = Some(0x6d0f_1a3c_58e2_47b9);
+
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        self.value.stable_hash(field_address.child(0), state);
+    }
+}
+
+struct Feet {
+    value: u32,
+}
+
+impl StableHash for Feet {
+    const TYPE_ID: Option<u64> = Some(0x2b74_c9d0_e815_6f03);
+
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        self.value.stable_hash(field_address.child(0), state);
+    }
+}
+
+#[test]
+fn none_is_unchanged() {
+    equal!(
+        102568403942768160221811810082933398928, "3428a4134bfdac56aa04614504705b0ffd1d48f27777b109a793e5a641324212";
+        Plain { one: 5 },
+        (5u32, 0u32)
+    );
+}
+
+#[test]
+fn different_type_ids_do_not_collide() {
+    not_equal!(Meters { value: 5 }, Feet { value: 5 });
+    not_equal!(Meters { value: 5 }, Plain { one: 5 });
+    equal!(
+        172349918521237001278780154238270126796, "403b3c77ff55e9a968d4a2192bf708e9c27dff9bd1a9c00acbfee9d364641504";
+        Meters { value: 5 },
+        &Meters { value: 5 }
+    );
+}
+
+#[test]
+fn nested_values_ignore_type_id() {
+    equal!(
+        217266355615266656602883701661187810150, "841a4d18ec43909c2de266a1e6a0bef32b84a789f47ffeb8b1521b1650e90815";
+        (Meters { value: 5 }, 1u32),
+        (Feet { value: 5 }, 1u32),
+        (Plain { one: 5 }, 1u32)
+    );
+}
+
+#[test]
+fn once_cells_forward_type_id() {
+    let cell = std::cell::OnceCell::new();
+    assert!(cell.set(Meters { value: 5 }).is_ok());
+    let lock = std::sync::OnceLock::new();
+    assert!(lock.set(Meters { value: 5 }).is_ok());
+    equal!(
+        172349918521237001278780154238270126796, "403b3c77ff55e9a968d4a2192bf708e9c27dff9bd1a9c00acbfee9d364641504";
+        Meters { value: 5 },
+        cell,
+        lock
+    );
+}
+
+#[test]
+fn transparent_wrappers_forward_type_id() {
+    use std::borrow::Cow;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    let rc = Rc::new(Meters { value: 5 });
+    let arc = Arc::new(Meters { value: 5 });
+    equal!(
+        172349918521237001278780154238270126796, "403b3c77ff55e9a968d4a2192bf708e9c27dff9bd1a9c00acbfee9d364641504";
+        Meters { value: 5 },
+        Box::new(Meters { value: 5 }),
+        Rc::downgrade(&rc),
+        Arc::downgrade(&arc),
+        Cow::Borrowed(&Meters { value: 5 }),
+        Cow::<Meters>::Owned(Meters { value: 5 })
+    );
+}
+
+struct ValidatedMeters(Meters);
+
+stable_hash::impl_stable_hash_transparent!(ValidatedMeters(Meters));
+
+struct CheckedMeters(Meters);
+
+stable_hash::impl_stable_hash!(CheckedMeters(transparent Meters));
+
+enum Length {
+    Unknown,
+    Metric(Meters),
+}
+
+stable_hash::impl_stable_hash_enum!(Length {
+    Unknown = 0,
+    #[stable_hash(transparent)]
+    Metric(value: Meters),
+});
+
+#[test]
+fn transparent_macros_forward_type_id() {
+    equal!(
+        172349918521237001278780154238270126796, "403b3c77ff55e9a968d4a2192bf708e9c27dff9bd1a9c00acbfee9d364641504";
+        Meters { value: 5 },
+        ValidatedMeters(Meters { value: 5 }),
+        CheckedMeters(Meters { value: 5 }),
+        Length::Metric(Meters { value: 5 })
+    );
+    not_equal!(Length::Unknown, Length::Metric(Meters { value: 5 }));
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "unicode-normalization")]
+
+mod common;
+
+use stable_hash::utils::NormalizedStr;
+
+const COMPOSED: &str = "caf\u{e9}";
+const DECOMPOSED: &str = "cafe\u{301}";
+
+#[test]
+fn equivalent_strings_match_when_normalized() {
+    equal!(
+        80169273156853942161693301971160556073, "0f4cc17863d4f7590f52656062316321ab7239bf624567a6c2d6979628748cdf";
+        NormalizedStr(COMPOSED),
+        NormalizedStr(DECOMPOSED),
+        COMPOSED
+    );
+}
+
+#[test]
+fn plain_str_is_byte_exact() {
+    not_equal!(COMPOSED, DECOMPOSED);
+}
+
+#[test]
+fn empty_is_default() {
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        NormalizedStr(""),
+        ""
+    );
+}
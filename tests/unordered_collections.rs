@@ -1,6 +1,16 @@
 mod common;
 
-use std::collections::{HashMap, HashSet};
+use fnv::FnvBuildHasher;
+use stable_hash::crypto::CryptoStableHasher;
+#[cfg(feature = "crypto-v2")]
+use stable_hash::crypto_stable_hash_v2;
+use stable_hash::fast::FastStableHasher;
+use stable_hash::prelude::*;
+use stable_hash::utils::{MapHashState, SortedHeap};
+use stable_hash::{crypto_stable_hash, fast_stable_hash};
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet};
+use std::hash::{BuildHasher, BuildHasherDefault, Hasher};
 
 macro_rules! map(
     { $($key:expr => $value:expr),* } => {
@@ -14,6 +24,18 @@ macro_rules! map(
      };
 );
 
+macro_rules! btree_map(
+    { $($key:expr => $value:expr),* } => {
+        {
+            let mut m = BTreeMap::new();
+            $(
+                m.insert($key, $value);
+            )*
+            m
+        }
+     };
+);
+
 macro_rules! set(
     { $($item:expr),* } => {
         {
@@ -76,6 +98,35 @@ fn hash_set_eq() {
     );
 }
 
+#[test]
+fn btree_set_matches_hash_set() {
+    equal!(
+        261168114195377271993952934537749440506, "4a87fcf3748ef16f7ebd64f1547d757a0b74c26d06a3368bcc03a8fce77734ef";
+        [3, 1, 2].into_iter().collect::<BTreeSet<_>>(),
+        set!{1, 2, 3}
+    );
+}
+
+#[test]
+fn set_type_of_map_key_does_not_matter() {
+    let btree_keys: BTreeMap<BTreeSet<u32>, &str> = btree_map! {
+        [1, 2].into_iter().collect() => "a",
+        [3].into_iter().collect() => "b",
+        BTreeSet::new() => "c"
+    };
+    // HashSet is not Hash, so it cannot be the key of a std map. But, the
+    // hash of the map only depends on the hashes of its entries.
+    let hash_keys: Vec<(HashSet<u32>, &str)> =
+        vec![(set! {2, 1}, "a"), (set! {3}, "b"), (HashSet::new(), "c")];
+    let state = MapHashState::from_entries(hash_keys.iter().map(|(k, v)| (k, v)));
+    assert_eq!(state.finish(), fast_stable_hash(&btree_keys));
+
+    not_equal!(
+        btree_keys,
+        btree_map! { [1, 2].into_iter().collect::<BTreeSet<u32>>() => "a" }
+    );
+}
+
 #[test]
 fn hash_set_ne_count() {
     not_equal!(set! {0, 1, 2}, set! {1, 2})
@@ -85,3 +136,282 @@ fn hash_set_ne_count() {
 fn hash_set_ne_item() {
     not_equal!(set! {1, 2}, set! {3, 2})
 }
+
+#[test]
+fn btree_map_key_widening() {
+    equal!(
+        43437583617372270790909720835359268929, "ccdfa17f49b975fb3d667c8d3e621691386c8834d9528d6702c056193e720e28";
+        btree_map!{ 5u16 => "five", 300u16 => "three hundred" },
+        btree_map!{ 5u32 => "five", 300u32 => "three hundred" },
+        btree_map!{ 5u64 => "five", 300u64 => "three hundred" },
+        map!{ 300u32 => "three hundred", 5u32 => "five" }
+    );
+}
+
+#[test]
+fn btree_map_ne_key() {
+    not_equal!(btree_map! { 5u16 => "five" }, btree_map! { 6u32 => "five" });
+}
+
+/// A deliberately poor hasher, so that the map's internal layout differs
+/// significantly from the other maps.
+#[derive(Default)]
+struct XorHasher(u64);
+
+impl Hasher for XorHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = self.0.rotate_left(5) ^ (*byte as u64);
+        }
+    }
+}
+
+fn fill<S: BuildHasher>(
+    mut map: HashMap<String, u64, S>,
+    reverse: bool,
+) -> HashMap<String, u64, S> {
+    let mut keys: Vec<u64> = (0..200).collect();
+    if reverse {
+        keys.reverse();
+    }
+    for key in keys {
+        map.insert(format!("key {}", key), key * 3);
+    }
+    map
+}
+
+#[test]
+fn hash_map_independent_of_order_capacity_and_hasher() {
+    let expected = fill(HashMap::new(), false);
+    let expected_fast = fast_stable_hash(&expected);
+    let expected_crypto = crypto_stable_hash(&expected);
+
+    let mut reserved = HashMap::new();
+    reserved.reserve(10_000);
+    let mut shrunk = fill(HashMap::with_capacity(5000), true);
+    shrunk.shrink_to_fit();
+
+    let maps = vec![
+        fill(HashMap::new(), true),
+        fill(HashMap::with_capacity(1), false),
+        fill(HashMap::with_capacity(1000), true),
+        fill(reserved, false),
+        shrunk,
+        // RandomState is seeded differently for each instance
+        fill(HashMap::with_hasher(RandomState::new()), false),
+        fill(HashMap::with_hasher(RandomState::new()), true),
+    ];
+    for map in &maps {
+        assert_eq!(expected_fast, fast_stable_hash(map));
+        assert_eq!(expected_crypto, crypto_stable_hash(map));
+    }
+
+    let default_hasher: HashMap<_, _, BuildHasherDefault<DefaultHasher>> =
+        fill(HashMap::default(), true);
+    assert_eq!(expected_fast, fast_stable_hash(&default_hasher));
+    assert_eq!(expected_crypto, crypto_stable_hash(&default_hasher));
+
+    let xor_hasher: HashMap<_, _, BuildHasherDefault<XorHasher>> = fill(
+        HashMap::with_capacity_and_hasher(3, Default::default()),
+        false,
+    );
+    assert_eq!(expected_fast, fast_stable_hash(&xor_hasher));
+    assert_eq!(expected_crypto, crypto_stable_hash(&xor_hasher));
+}
+
+#[test]
+fn binary_heap_eq() {
+    equal!(
+        261168114195377271993952934537749440506, "4a87fcf3748ef16f7ebd64f1547d757a0b74c26d06a3368bcc03a8fce77734ef";
+        BinaryHeap::from(vec![1, 2, 3]),
+        BinaryHeap::from(vec![3, 1, 2]),
+        set!{1, 2, 3}
+    );
+}
+
+#[test]
+fn binary_heap_duplicates_ne() {
+    not_equal!(
+        BinaryHeap::from(vec![1, 2, 2]),
+        BinaryHeap::from(vec![1, 2])
+    );
+}
+
+#[test]
+fn sorted_heap_matches_sorted_vec() {
+    let a = BinaryHeap::from(vec![5u32, 0, 9, 3, 3]);
+    let mut b = BinaryHeap::new();
+    for item in [3u32, 9, 3, 0, 5] {
+        b.push(item);
+    }
+    equal!(
+        233150726389442668907984335883834576814, "1905ecc61c9b26e1f948c93319015d0ae3b6711289c6120fce5b8669e949f20c";
+        SortedHeap(&a),
+        SortedHeap(&b),
+        vec![0u32, 3, 3, 5, 9]
+    );
+    not_equal!(SortedHeap(&a), a);
+}
+
+/// The general construction for a map: each entry is hashed as a (key, value)
+/// tuple into its own hasher, and the serialized state of that hasher is
+/// written at the map's address.
+fn reference_map_hash<H: StableHasher>(map: &HashMap<u32, u64>) -> H::Out {
+    let mut state = H::new();
+    for entry in map {
+        let (a, b) = H::Addr::root().unordered();
+        let mut entry_hasher = H::new();
+        entry.stable_hash(a, &mut entry_hasher);
+        state.write(b, entry_hasher.to_bytes().as_ref());
+    }
+    state.finish()
+}
+
+#[test]
+fn small_maps_match_general_construction() {
+    for size in 0..=8u32 {
+        let map: HashMap<u32, u64> = (0..size).map(|k| (k, k as u64 * 7 + 1)).collect();
+        let mut reversed = HashMap::with_capacity(64);
+        reversed.extend((0..size).rev().map(|k| (k, k as u64 * 7 + 1)));
+
+        let fast = reference_map_hash::<FastStableHasher>(&map);
+        let crypto = reference_map_hash::<CryptoStableHasher>(&map);
+        for map in [&map, &reversed] {
+            assert_eq!(fast, fast_stable_hash(map));
+            assert_eq!(crypto, crypto_stable_hash(map));
+        }
+    }
+}
+
+#[test]
+fn unit_map_is_set() {
+    let keys = ["a", "b", "c"];
+    let unit_map: HashMap<_, _> = keys.iter().map(|k| (k.to_string(), ())).collect();
+    let unit_btree_map: BTreeMap<_, _> = keys.iter().map(|k| (k.to_string(), ())).collect();
+    let set: HashSet<_> = keys.iter().map(|k| k.to_string()).collect();
+    equal!(
+        67123755585842743557241812785275078909, "1bf897e3d2f775d751fc70bbd953ff4df584731c3e1a99e7980f17cef80ca0d9";
+        unit_map,
+        unit_btree_map,
+        set
+    );
+
+    let empty: HashMap<u32, ()> = HashMap::new();
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        empty,
+        HashSet::<u32>::new(),
+        ()
+    );
+}
+
+#[test]
+fn unit_map_through_pointer_is_set() {
+    let set: HashSet<u32> = (0..5).collect();
+    let unit = ();
+    let borrowed: HashMap<u32, &()> = (0..5).map(|k| (k, &unit)).collect();
+    let boxed: HashMap<u32, Box<()>> = (0..5).map(|k| (k, Box::new(()))).collect();
+    let shared: BTreeMap<u32, std::sync::Arc<()>> =
+        (0..5).map(|k| (k, Default::default())).collect();
+    let nested: HashMap<u32, std::rc::Rc<&()>> =
+        (0..5).map(|k| (k, std::rc::Rc::new(&unit))).collect();
+    equal!(
+        196007446561223753396017284103740792562, "da1ed0d1ab70c5f2075d93cf39fdbeb7e4e609f4fb2f74679c47c7c568e2559a";
+        set,
+        borrowed,
+        boxed,
+        shared,
+        nested
+    );
+}
+
+#[test]
+fn unit_map_ne_map_to_default() {
+    // Only () is treated as a set. Other values which write nothing are still entries.
+    not_equal!(map! { 1u32 => () }, map! { 1u32 => 0u32 });
+}
+
+#[test]
+fn hash_set_independent_of_build_hasher() {
+    let items = (0..100u32).map(|i| format!("item {}", i));
+    let random_state: HashSet<String> = items.clone().collect();
+    let fnv: HashSet<String, FnvBuildHasher> = items.clone().collect();
+    let xor: HashSet<String, BuildHasherDefault<XorHasher>> = items.rev().collect();
+
+    assert_eq!(fast_stable_hash(&random_state), fast_stable_hash(&fnv));
+    assert_eq!(crypto_stable_hash(&random_state), crypto_stable_hash(&fnv));
+    assert_eq!(fast_stable_hash(&random_state), fast_stable_hash(&xor));
+    assert_eq!(crypto_stable_hash(&random_state), crypto_stable_hash(&xor));
+}
+
+#[test]
+fn ordered_ne_unordered() {
+    not_equal!(vec![1u32, 2], set! {1u32, 2});
+    not_equal!(vec![2u32, 1], set! {1u32, 2});
+    not_equal!(vec![1u32], set! {1u32});
+    not_equal!(set! {0u32}, HashSet::<u32>::new());
+    not_equal!(vec![(1u32, "one")], map! {1u32 => "one"});
+    not_equal!(
+        std::collections::VecDeque::from(vec![1u32, 2]),
+        [1u32, 2].into_iter().collect::<BTreeSet<_>>()
+    );
+}
+
+#[test]
+fn empty_ordered_eq_empty_unordered() {
+    // Empty collections are all the default, and write nothing. This is what
+    // allows a collection field to be added without changing the hash.
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        Vec::<u32>::new(),
+        HashSet::<u32>::new(),
+        BTreeSet::<u32>::new()
+    );
+}
+
+/// Not equal with the backends which write a default member distinctly
+fn default_member_not_equal<T: StableHash, U: StableHash>(ordered: T, unordered: U) {
+    assert_ne!(fast_stable_hash(&ordered), fast_stable_hash(&unordered));
+    #[cfg(feature = "crypto-v2")]
+    assert_ne!(
+        crypto_stable_hash_v2(&ordered),
+        crypto_stable_hash_v2(&unordered)
+    );
+}
+
+/// Records the known collision of the default crypto backend. A default member
+/// writes nothing on its own. CryptoStableHasher writes the state of an empty
+/// hasher for it, which is the same as the int 1, and at the same address as the
+/// set itself. If this starts failing, the member encoding of CryptoStableHasher
+/// has changed, which changes persisted hashes. See also CryptoStableHasherV2
+#[test]
+fn crypto_default_member_collides() {
+    let set = set! {0u32};
+    assert_eq!(crypto_stable_hash(&vec![0u32]), crypto_stable_hash(&set));
+    assert_eq!(crypto_stable_hash(&1u32), crypto_stable_hash(&set));
+    assert_eq!(
+        crypto_stable_hash(&vec![""]),
+        crypto_stable_hash(&set! {""})
+    );
+}
+
+#[test]
+fn default_member_ne_ordered() {
+    default_member_not_equal(vec![0u32], set! {0u32});
+    default_member_not_equal(vec![""], set! {""});
+    default_member_not_equal(1u32, set! {0u32});
+    default_member_not_equal(Ok::<u32, u32>(0), set! {0u32});
+    default_member_not_equal(HashSet::<u32>::new(), set! {0u32});
+}
+
+#[cfg(feature = "crypto-v2")]
+#[test]
+fn crypto_v2_matches_crypto_without_default_member() {
+    let value = (set! {1u32, 2}, map! {"a" => set! {3u32}}, 0u32, vec![0u32]);
+    assert_eq!(crypto_stable_hash(&value), crypto_stable_hash_v2(&value));
+    let value = (set! {0u32, 1}, 1u32);
+    assert_ne!(crypto_stable_hash(&value), crypto_stable_hash_v2(&value));
+}
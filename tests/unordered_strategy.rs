@@ -0,0 +1,30 @@
+use stable_hash::{crypto_stable_hash, fast_stable_hash};
+use std::collections::{BTreeSet, BinaryHeap, HashSet};
+
+// Each member of an unordered collection is hashed independently and the results
+// are combined in a way that does not depend on order. So visiting the members in
+// sorted order (as BTreeSet does) and in arbitrary order (as HashSet does) must
+// agree at every size, which is why the impls need no strategy by size.
+#[test]
+fn visit_order_does_not_matter() {
+    for size in 0..=100u32 {
+        // Reversed, so that the HashSet is not built from sorted input
+        let items: Vec<String> = (0..size).rev().map(|i| format!("item {}", i)).collect();
+
+        let sorted: BTreeSet<&String> = items.iter().collect();
+        let online: HashSet<&String> = items.iter().collect();
+        assert_eq!(fast_stable_hash(&sorted), fast_stable_hash(&online));
+        assert_eq!(crypto_stable_hash(&sorted), crypto_stable_hash(&online));
+    }
+}
+
+#[test]
+fn visit_order_does_not_matter_with_duplicates() {
+    let items = vec![3u32, 1, 3, 2, 1];
+    let mut sorted = items.clone();
+    sorted.sort_unstable();
+    let heap = BinaryHeap::from(items.clone());
+    let sorted_heap = BinaryHeap::from(sorted);
+    assert_eq!(fast_stable_hash(&heap), fast_stable_hash(&sorted_heap));
+    assert_eq!(crypto_stable_hash(&heap), crypto_stable_hash(&sorted_heap));
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use std::collections::VecDeque;
+
+#[test]
+fn matches_vec() {
+    // Wrapped around the end of the buffer, but in the same logical order
+    let mut wrapped = VecDeque::with_capacity(4);
+    wrapped.push_back(3u32);
+    wrapped.push_front(2);
+    wrapped.push_front(1);
+    equal!(
+        193833936596083547035584674840075484437, "f06836982af48669f211bf9c60bd27f8326540d528bd8ea9fda5f25d8b283a54";
+        VecDeque::from(vec![1u32, 2, 3]),
+        wrapped,
+        vec![1u32, 2, 3]
+    );
+}
+
+#[test]
+fn rotation_changes_hash() {
+    // Users wanting rotation independence should use an unordered collection
+    let deque = VecDeque::from(vec![1u32, 2, 3]);
+    let mut rotated = deque.clone();
+    rotated.rotate_left(1);
+    not_equal!(deque, rotated);
+}
+
+#[test]
+fn trailing_defaults_are_counted() {
+    not_equal!(
+        VecDeque::from(vec![true, false]),
+        VecDeque::from(vec![true, false, false])
+    );
+}
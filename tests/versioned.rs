@@ -0,0 +1,54 @@
+mod common;
+
+use stable_hash::prelude::*;
+use stable_hash::utils::Versioned;
+
+struct Meters(u32);
+
+impl StableHash for Meters {
+    const TYPE_ID: Option<u64> = Some(0x6d0f_1a3c_58e2_47b9);
+
+    fn stable_hash<H: StableHasher>(&self, field_address: H::Addr, state: &mut H) {
+        self.0.stable_hash(field_address.child(0), state);
+    }
+}
+
+#[test]
+fn versions_do_not_collide() {
+    not_equal!(Versioned::<1, _>("abc"), Versioned::<2, _>("abc"));
+    not_equal!(Versioned::<1, _>("abc"), "abc");
+    not_equal!(Versioned::<1, _>(5u32), (0u32, 5u32));
+}
+
+#[test]
+fn version_zero_is_baseline() {
+    equal!(
+        225126920276043178050448780660223346301, "fe0d5d84754701b6cbb91451f9d8c6e589219ad8c04096ba7e4fe2e303671d59";
+        Versioned::<0, _>("abc"),
+        "abc"
+    );
+}
+
+#[test]
+fn nested_version_changes_container() {
+    not_equal!(
+        vec![Versioned::<1, _>(1u32), Versioned::<1, _>(2u32)],
+        vec![Versioned::<2, _>(1u32), Versioned::<2, _>(2u32)]
+    );
+    // The default still writes nothing
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        Versioned::<3, _>(0u32),
+        ()
+    );
+}
+
+#[test]
+fn type_id_is_forwarded() {
+    equal!(
+        172349918521237001278780154238270126796, "403b3c77ff55e9a968d4a2192bf708e9c27dff9bd1a9c00acbfee9d364641504";
+        Versioned::<0, _>(Meters(5)),
+        Meters(5)
+    );
+    not_equal!(Versioned::<0, _>(Meters(5)), (5u32,));
+}
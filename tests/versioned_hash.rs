@@ -0,0 +1,25 @@
+use stable_hash::{fast_stable_hash_versioned, schema_version_of, VersionedHash};
+
+#[test]
+fn versions_do_not_collide() {
+    let v1 = fast_stable_hash_versioned(&"abc", 1);
+    let v2 = fast_stable_hash_versioned(&"abc", 2);
+    assert_ne!(v1, v2);
+    assert_ne!(v1.value, v2.value);
+    assert_eq!(schema_version_of(&v1), 1);
+    assert_eq!(schema_version_of(&v2), 2);
+}
+
+#[test]
+fn detect_stale() {
+    const CURRENT: u32 = 3;
+    let stored = fast_stable_hash_versioned(&vec![1u32, 2u32], 2);
+    assert_ne!(schema_version_of(&stored), CURRENT);
+    assert_eq!(
+        stored,
+        VersionedHash {
+            schema_version: 2,
+            value: 332899483166285567240630984814951142399,
+        }
+    );
+}
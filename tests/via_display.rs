@@ -0,0 +1,28 @@
+mod common;
+
+use stable_hash::utils::ViaDisplay;
+
+#[test]
+fn matches_str() {
+    equal!(
+        57712703087837059854423775877667884101, "155a539e8c2560a3c9d5b03687f42e789258ae76c6411479238a03dff963786f";
+        ViaDisplay(42u32),
+        ViaDisplay("42"),
+        "42"
+    );
+}
+
+#[test]
+fn hashes_formatting_not_value() {
+    // The formatting, not the number, is what gets hashed
+    not_equal!(ViaDisplay(42u32), 42u32);
+}
+
+#[test]
+fn different_types_may_collide() {
+    // 1.0f64 is displayed as "1", so it cannot be told apart from 1u32
+    assert_eq!(
+        common::fast_stable_hash(&ViaDisplay(1.0f64)),
+        common::fast_stable_hash(&ViaDisplay(1u32))
+    );
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use std::rc::{self, Rc};
+use std::sync::{self, Arc};
+
+#[test]
+fn live_weak_matches_value() {
+    let strong = Rc::new("abc".to_string());
+    let shared = Arc::new("abc".to_string());
+    equal!(
+        225126920276043178050448780660223346301, "fe0d5d84754701b6cbb91451f9d8c6e589219ad8c04096ba7e4fe2e303671d59";
+        Rc::downgrade(&strong),
+        Arc::downgrade(&shared),
+        "abc".to_string()
+    );
+}
+
+#[test]
+fn dead_weak_is_default() {
+    let strong = Rc::new("abc".to_string());
+    let weak = Rc::downgrade(&strong);
+    let shared = Arc::new("abc".to_string());
+    let shared_weak = Arc::downgrade(&shared);
+    drop(strong);
+    drop(shared);
+    equal!(
+        320514965852340112707580934281173047643, "48fc721fbbc172e0925fa27af1671de225ba927134802998b10a1568a188652b";
+        weak,
+        shared_weak,
+        rc::Weak::<String>::new(),
+        sync::Weak::<String>::new(),
+        String::new(),
+        Option::<String>::None
+    );
+}
+
+#[test]
+fn dropping_target_changes_hash() {
+    let strong = Rc::new(5u32);
+    let fields = (Rc::downgrade(&strong), 1u32);
+    let before = common::fast_stable_hash(&fields);
+    drop(strong);
+    assert_ne!(before, common::fast_stable_hash(&fields));
+}